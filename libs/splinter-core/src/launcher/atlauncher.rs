@@ -0,0 +1,36 @@
+use std::path::{Path, PathBuf};
+
+use crate::launcher::{Instance, LauncherProvider, dir_name, read_json, subdirs};
+
+pub struct AtLauncher;
+
+impl LauncherProvider for AtLauncher {
+    fn name(&self) -> &'static str {
+        "ATLauncher"
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![];
+        dirs.extend(dirs::data_dir().map(|dir| dir.join("ATLauncher")));
+        dirs.extend(
+            dirs::home_dir().map(|dir| dir.join(".var/app/com.atlauncher.ATLauncher/data")),
+        );
+        dirs
+    }
+
+    fn instances(&self, data_dir: &Path) -> Vec<Instance> {
+        subdirs(&data_dir.join("instances"))
+            .into_iter()
+            .map(|dir| {
+                let name = read_json(&dir.join("instance.json"))
+                    .and_then(|instance| Some(instance["launcher"]["name"].as_str()?.to_string()))
+                    .unwrap_or_else(|| dir_name(&dir));
+                Instance {
+                    launcher: self.name(),
+                    name,
+                    mods: dir.join("mods"),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+
+use crate::launcher::{Instance, LauncherProvider, dir_name, read_json, subdirs};
+
+/// The CurseForge app.
+///
+/// It keeps a custom instance location in its own settings store, which is not readable,
+/// so instances moved there have to be opened by their path.
+pub struct CurseForge;
+
+impl LauncherProvider for CurseForge {
+    fn name(&self) -> &'static str {
+        "CurseForge"
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        // In the home on Windows, and in the documents on macOS.
+        [dirs::home_dir(), dirs::document_dir()]
+            .into_iter()
+            .flatten()
+            .map(|dir| dir.join("curseforge/minecraft"))
+            .collect()
+    }
+
+    fn instances(&self, data_dir: &Path) -> Vec<Instance> {
+        subdirs(&data_dir.join("Instances"))
+            .into_iter()
+            .map(|dir| {
+                let name = read_json(&dir.join("minecraftinstance.json"))
+                    .and_then(|instance| Some(instance["name"].as_str()?.to_string()))
+                    .unwrap_or_else(|| dir_name(&dir));
+                Instance {
+                    launcher: self.name(),
+                    name,
+                    mods: dir.join("mods"),
+                }
+            })
+            .collect()
+    }
+}
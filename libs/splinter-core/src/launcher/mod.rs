@@ -0,0 +1,114 @@
+use std::collections::HashSet;
+use std::fs::{read_dir, read_to_string};
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use atlauncher::AtLauncher;
+use curseforge::CurseForge;
+use modrinth::ModrinthApp;
+use multimc::MmcLauncher;
+use vanilla::Vanilla;
+
+mod atlauncher;
+mod curseforge;
+mod modrinth;
+mod multimc;
+mod vanilla;
+
+/// An instance of a launcher, which can be opened as a modpack.
+#[derive(Clone, Debug)]
+pub struct Instance {
+    pub launcher: &'static str,
+    pub name: String,
+    /// The mods folder of the instance.
+    pub mods: PathBuf,
+}
+
+/// Finds the instances of one launcher.
+pub trait LauncherProvider {
+    fn name(&self) -> &'static str;
+
+    /// Where the launcher keeps its data when installed the usual way, these may not exist.
+    fn data_dirs(&self) -> Vec<PathBuf>;
+
+    /// The instances in a data dir of the launcher, including the ones in an instance dir configured in the launcher.
+    fn instances(&self, data_dir: &Path) -> Vec<Instance>;
+}
+
+pub fn providers() -> Vec<Box<dyn LauncherProvider>> {
+    vec![
+        Box::new(MmcLauncher::prism()),
+        Box::new(MmcLauncher::multimc()),
+        Box::new(ModrinthApp),
+        Box::new(CurseForge),
+        Box::new(AtLauncher),
+        Box::new(Vanilla),
+    ]
+}
+
+/// Finds the instances of every known launcher which have a mods folder.
+pub fn discover() -> Vec<Instance> {
+    let mut seen = HashSet::new();
+    let mut instances = vec![];
+    for provider in providers() {
+        for data_dir in provider.data_dirs() {
+            if !data_dir.is_dir() {
+                continue;
+            }
+
+            let found = provider.instances(&data_dir);
+            debug!(
+                "Found {} {} instances in {data_dir:?}",
+                found.len(),
+                provider.name()
+            );
+            for instance in found {
+                // Data dirs can be symlinked to each other, like a flatpak and a native install.
+                let mods = instance
+                    .mods
+                    .canonicalize()
+                    .unwrap_or(instance.mods.clone());
+                if instance.mods.is_dir() && seen.insert(mods) {
+                    instances.push(instance);
+                }
+            }
+        }
+    }
+
+    instances.sort_by_cached_key(|instance| (instance.launcher, instance.name.to_lowercase()));
+    instances
+}
+
+/// The directories in the directory.
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&read_to_string(path).ok()?).ok()
+}
+
+/// The name of the directory, for instances which do not store their name.
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Resolves a directory from a launcher config, which are relative to the data dir of the launcher.
+fn config_dir(data_dir: &Path, value: &str) -> PathBuf {
+    match value.strip_prefix("~/") {
+        Some(path) => dirs::home_dir().unwrap_or_default().join(path),
+        None => data_dir.join(value),
+    }
+}
@@ -0,0 +1,45 @@
+use std::path::{Path, PathBuf};
+
+use crate::launcher::{Instance, LauncherProvider, dir_name, read_json, subdirs};
+
+/// The Modrinth App, which calls its instances profiles.
+pub struct ModrinthApp;
+
+impl LauncherProvider for ModrinthApp {
+    fn name(&self) -> &'static str {
+        "Modrinth App"
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        let Some(data_dir) = dirs::data_dir() else {
+            return vec![];
+        };
+        // Versions before 0.8 are named after the old project name.
+        vec![
+            data_dir.join("ModrinthApp"),
+            data_dir.join("com.modrinth.theseus"),
+        ]
+    }
+
+    fn instances(&self, data_dir: &Path) -> Vec<Instance> {
+        // The app can move its profiles to another directory, which older versions keep in their settings.
+        let profiles_dir = read_json(&data_dir.join("settings.json"))
+            .and_then(|settings| Some(PathBuf::from(settings["loaded_config_dir"].as_str()?)))
+            .unwrap_or(data_dir.to_path_buf())
+            .join("profiles");
+
+        subdirs(&profiles_dir)
+            .into_iter()
+            .map(|dir| {
+                let name = read_json(&dir.join("profile.json"))
+                    .and_then(|profile| Some(profile["metadata"]["name"].as_str()?.to_string()))
+                    .unwrap_or_else(|| dir_name(&dir));
+                Instance {
+                    launcher: self.name(),
+                    name,
+                    mods: dir.join("mods"),
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,118 @@
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use crate::launcher::{Instance, LauncherProvider, config_dir, dir_name, subdirs};
+
+/// MultiMC and its forks, which all share the instance format.
+pub struct MmcLauncher {
+    name: &'static str,
+    /// The directory in the data dir of the system.
+    folder: &'static str,
+    /// The config in the data dir of the launcher, which can move the instances somewhere else.
+    config: &'static str,
+    flatpak: Option<&'static str>,
+}
+
+impl MmcLauncher {
+    pub fn prism() -> MmcLauncher {
+        MmcLauncher {
+            name: "Prism Launcher",
+            folder: "PrismLauncher",
+            config: "prismlauncher.cfg",
+            flatpak: Some("org.prismlauncher.PrismLauncher"),
+        }
+    }
+
+    pub fn multimc() -> MmcLauncher {
+        MmcLauncher {
+            name: "MultiMC",
+            folder: "multimc",
+            config: "multimc.cfg",
+            flatpak: None,
+        }
+    }
+}
+
+impl LauncherProvider for MmcLauncher {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![];
+        dirs.extend(dirs::data_dir().map(|dir| dir.join(self.folder)));
+        if let (Some(home), Some(flatpak)) = (dirs::home_dir(), self.flatpak) {
+            dirs.push(
+                home.join(".var/app")
+                    .join(flatpak)
+                    .join("data")
+                    .join(self.folder),
+            );
+        }
+        dirs
+    }
+
+    fn instances(&self, data_dir: &Path) -> Vec<Instance> {
+        let config = read_to_string(data_dir.join(self.config)).unwrap_or_default();
+        let instance_dir = cfg_value(&config, "InstanceDir").unwrap_or("instances");
+
+        subdirs(&config_dir(data_dir, instance_dir))
+            .into_iter()
+            .filter_map(|dir| {
+                let config = read_to_string(dir.join("instance.cfg")).ok()?;
+                // Older versions use .minecraft, newer ones minecraft.
+                let game_dir = [".minecraft", "minecraft"]
+                    .into_iter()
+                    .map(|name| dir.join(name))
+                    .find(|path| path.is_dir())?;
+                Some(Instance {
+                    launcher: self.name,
+                    name: cfg_value(&config, "name").map_or_else(|| dir_name(&dir), str::to_string),
+                    mods: game_dir.join("mods"),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reads a value of the ini-like configs of MultiMC.
+fn cfg_value<'a>(config: &'a str, key: &str) -> Option<&'a str> {
+    config.lines().find_map(|line| {
+        let (name, value) = line.split_once('=')?;
+        (name.trim() == key && !value.trim().is_empty()).then(|| value.trim())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::create_dir_all;
+
+    use crate::launcher::LauncherProvider;
+    use crate::launcher::multimc::MmcLauncher;
+    use crate::test_util::{write_file, TempDir};
+
+    #[test]
+    fn custom_instance_dir() {
+        let data_dir = TempDir::new("launcher-multimc");
+        write_file(
+            &data_dir.join("prismlauncher.cfg"),
+            "[General]\nInstanceDir=custom\n",
+        );
+        write_file(
+            &data_dir.join("custom/abc/instance.cfg"),
+            "InstanceType=OneSix\nname=My Pack\n",
+        );
+        create_dir_all(data_dir.join("custom/abc/minecraft/mods")).unwrap();
+        // Not an instance, and an instance in the default dir which is not used.
+        create_dir_all(data_dir.join("custom/.tmp")).unwrap();
+        write_file(&data_dir.join("instances/old/instance.cfg"), "name=Old\n");
+
+        let instances = MmcLauncher::prism().instances(&data_dir);
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].name, "My Pack");
+        assert_eq!(
+            instances[0].mods,
+            data_dir.join("custom/abc/minecraft/mods")
+        );
+    }
+}
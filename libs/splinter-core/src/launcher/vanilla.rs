@@ -0,0 +1,50 @@
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::launcher::{Instance, LauncherProvider, config_dir, read_json};
+
+/// The official launcher, where every installation with its own game directory is an instance.
+pub struct Vanilla;
+
+impl LauncherProvider for Vanilla {
+    fn name(&self) -> &'static str {
+        "Minecraft Launcher"
+    }
+
+    fn data_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![];
+        // .minecraft in the app data on Windows, minecraft in the application support on macOS.
+        if let Some(data_dir) = dirs::data_dir() {
+            dirs.push(data_dir.join(".minecraft"));
+            dirs.push(data_dir.join("minecraft"));
+        }
+        dirs.extend(dirs::home_dir().map(|dir| dir.join(".minecraft")));
+        dirs
+    }
+
+    fn instances(&self, data_dir: &Path) -> Vec<Instance> {
+        let mut instances = vec![Instance {
+            launcher: self.name(),
+            name: "Default".to_string(),
+            mods: data_dir.join("mods"),
+        }];
+
+        let profiles = read_json(&data_dir.join("launcher_profiles.json"));
+        if let Some(Value::Object(profiles)) = profiles.as_ref().map(|v| &v["profiles"]) {
+            for (key, profile) in profiles {
+                let Some(game_dir) = profile["gameDir"].as_str() else {
+                    continue;
+                };
+                let name = profile["name"].as_str().filter(|name| !name.is_empty());
+                instances.push(Instance {
+                    launcher: self.name(),
+                    name: name.unwrap_or(key).to_string(),
+                    mods: config_dir(data_dir, game_dir).join("mods"),
+                });
+            }
+        }
+
+        instances
+    }
+}
@@ -0,0 +1,27 @@
+//! Everything splinter needs to search a modpack, without a window:
+//! reading the mods and the instance, the search strategies and renaming the jars.
+
+mod launcher;
+mod modpack;
+mod plugin;
+#[cfg(test)]
+mod test_util;
+
+pub use launcher::*;
+pub use modpack::*;
+pub use plugin::*;
+
+/// Something the user should know about, shown as a toast by the app and printed by the command line.
+#[derive(Debug)]
+pub struct NotificationEvent {
+    pub title: String,
+    pub description: String,
+    pub ty: Severity,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use crate::modpack::PluginList;
+use crate::{Plugin, PluginMetadata};
+
+/// What changing the forced status of a mod does to the other mods, see [`PluginList::force_impact`].
+#[derive(Default, Debug, PartialEq)]
+pub struct ForceImpact {
+    /// Enabled mods which would no longer find a dependency, with the id of the dependency.
+    /// A mod also counts when it only loses the dependency because another mod in here does not load anymore.
+    pub missing_dependencies: Vec<(String, String)>,
+    /// The modules nested in the mod which would no longer be loaded by anything.
+    pub removed_modules: Vec<String>,
+    /// Pairs of mods which would load together, while the first one breaks the second.
+    pub breaks: Vec<(String, String)>,
+}
+
+impl ForceImpact {
+    pub fn is_empty(&self) -> bool {
+        self.missing_dependencies.is_empty() && self.removed_modules.is_empty() && self.breaks.is_empty()
+    }
+
+    /// The mods which have to be forced off along with the change to the mod, so none of this happens.
+    pub fn cascade(&self, id: &str) -> Vec<String> {
+        let mut cascade = Vec::new();
+        let dependants = self.missing_dependencies.iter().map(|(dependant, _)| dependant);
+        let incompatible = self
+            .breaks
+            .iter()
+            .map(|(v0, v1)| if v0 == id { v1 } else { v0 });
+        for other in dependants.chain(incompatible) {
+            if other != id && !cascade.contains(other) {
+                cascade.push(other.clone());
+            }
+        }
+        cascade
+    }
+}
+
+impl PluginList {
+    /// Finds what forcing the mod to the status would do, without changing anything.
+    pub fn force_impact(&self, id: &str, forced: Option<bool>) -> ForceImpact {
+        let mut impact = ForceImpact::default();
+        let Some(&index) = self.lookup.get(id) else {
+            return impact;
+        };
+        let plugin = &self.list[index];
+        let loaded = forced.unwrap_or(plugin.status.enabled());
+        if loaded == plugin.enabled() {
+            return impact;
+        }
+
+        if loaded {
+            for (i, other) in self.list.iter().enumerate() {
+                if i == index || !other.enabled() {
+                    continue;
+                }
+                if breaks(plugin, other) {
+                    impact.breaks.push((plugin.metadata.id.clone(), other.metadata.id.clone()));
+                }
+                if breaks(other, plugin) {
+                    impact.breaks.push((other.metadata.id.clone(), plugin.metadata.id.clone()));
+                }
+            }
+            return impact;
+        }
+
+        // The mods which are no longer going to load, they take the mods needing them down with them.
+        let mut gone = HashSet::from([index]);
+        let provided = |id: &str, gone: &HashSet<usize>| {
+            self.list.iter().enumerate().any(|(i, other)| {
+                other.enabled() && !gone.contains(&i) && other.metadata.module_version(id).is_some()
+            })
+        };
+        let mut nested = Vec::new();
+        nested_modules(&plugin.metadata, &mut nested);
+        impact.removed_modules = nested.into_iter().filter(|id| !provided(id, &gone)).collect();
+
+        let mut scan = true;
+        while scan {
+            scan = false;
+            for (i, other) in self.list.iter().enumerate() {
+                if !other.enabled() || gone.contains(&i) {
+                    continue;
+                }
+
+                // Dependencies which were missing before the change are reported elsewhere.
+                let lost = other.metadata.depends_on.iter().find(|dependency| {
+                    let was_provided = gone
+                        .iter()
+                        .any(|&i| self.list[i].metadata.module_version(&dependency.id).is_some());
+                    was_provided && !provided(&dependency.id, &gone)
+                });
+                if let Some(dependency) = lost {
+                    impact
+                        .missing_dependencies
+                        .push((other.metadata.id.clone(), dependency.id.clone()));
+                    gone.insert(i);
+                    // The mods depending on this one are only found on the next scan.
+                    scan = true;
+                }
+            }
+        }
+
+        impact
+    }
+}
+
+/// If the first plugin breaks any module of the second one.
+fn breaks(plugin: &Plugin, other: &Plugin) -> bool {
+    plugin.metadata.breaks.iter().any(|breaks| {
+        other
+            .metadata
+            .module_version(&breaks.id)
+            .is_some_and(|version| breaks.version.matches(version))
+    })
+}
+
+/// Adds the ids of the modules nested in the jar, and of the modules nested in those.
+fn nested_modules(metadata: &PluginMetadata, ids: &mut Vec<String>) {
+    for nested in &metadata.contains {
+        ids.push(nested.id.clone());
+        nested_modules(nested, ids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modpack::impact::ForceImpact;
+    use crate::modpack::PluginList;
+    use crate::test_util::{fabric_jar, write_file, TempDir};
+    use crate::Plugin;
+
+    /// Reads the mods the way the loader does, they all start out enabled.
+    fn plugins(name: &str, jars: &[(&str, Vec<u8>)]) -> PluginList {
+        let dir = TempDir::new(name);
+        let mut plugins = PluginList::new();
+        for (id, data) in jars {
+            let path = dir.join(format!("{id}.jar"));
+            write_file(&path, data);
+            let plugin = Plugin::new(path).unwrap().unwrap();
+            for id in [&plugin.metadata.id].into_iter().chain(&plugin.metadata.provides) {
+                plugins.lookup.insert(id.clone(), plugins.list.len());
+            }
+            plugins.list.push(plugin);
+        }
+        plugins
+    }
+
+    #[test]
+    fn missing_dependencies() {
+        let plugins = plugins(
+            "impact-dependencies",
+            &[
+                ("a", fabric_jar("a", "", &[])),
+                ("b", fabric_jar("b", r#", "depends": {"a": "*"}"#, &[])),
+                ("c", fabric_jar("c", r#", "depends": {"b": "*"}"#, &[])),
+                ("d", fabric_jar("d", r#", "depends": {"minecraft": "*"}"#, &[])),
+            ],
+        );
+
+        let impact = plugins.force_impact("a", Some(false));
+        assert_eq!(
+            impact.missing_dependencies,
+            [("b".to_string(), "a".to_string()), ("c".to_string(), "b".to_string())]
+        );
+        assert!(impact.removed_modules.is_empty() && impact.breaks.is_empty());
+        assert_eq!(impact.cascade("a"), ["b", "c"]);
+        // The mod is loaded already, so forcing it on does not change anything.
+        assert_eq!(plugins.force_impact("a", Some(true)), ForceImpact::default());
+        assert!(plugins.force_impact("d", Some(false)).is_empty());
+    }
+
+    #[test]
+    fn removed_modules() {
+        let nested = ("META-INF/jars/api-base.jar", fabric_jar("api-base", "", &[]));
+        let plugins = plugins(
+            "impact-nested",
+            &[
+                ("api", fabric_jar("api", r#", "jars": [{"file": "META-INF/jars/api-base.jar"}]"#, &[nested])),
+                ("user", fabric_jar("user", r#", "depends": {"api-base": "*"}"#, &[])),
+            ],
+        );
+
+        let impact = plugins.force_impact("api", Some(false));
+        assert_eq!(impact.removed_modules, ["api-base"]);
+        assert_eq!(impact.missing_dependencies, [("user".to_string(), "api-base".to_string())]);
+        assert_eq!(impact.cascade("api"), ["user"]);
+    }
+
+    #[test]
+    fn breaks() {
+        let mut plugins = plugins(
+            "impact-breaks",
+            &[
+                ("a", fabric_jar("a", r#", "breaks": {"b": "*"}"#, &[])),
+                ("b", fabric_jar("b", "", &[])),
+                // Only older versions of "a" are broken by it.
+                ("c", fabric_jar("c", r#", "breaks": {"a": "<1.0.0"}"#, &[])),
+            ],
+        );
+        plugins.get_mut("a").unwrap().forced_status = Some(false);
+
+        for forced in [Some(true), None] {
+            let impact = plugins.force_impact("a", forced);
+            assert_eq!(impact.breaks, [("a".to_string(), "b".to_string())]);
+            assert!(impact.missing_dependencies.is_empty() && impact.removed_modules.is_empty());
+            assert_eq!(impact.cascade("a"), ["b"]);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::fs::{read_dir, read_to_string, remove_file, rename};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, warn};
+
+use crate::modpack::{splinter_dir, write_file};
+
+/// A write-ahead log of the renames in the mods folder.
+///
+/// Planned renames are written down before they happen, if splinter stops halfway
+/// they get rolled back the next time the modpack is opened.
+/// It also remembers the file name every jar had before splinter touched it.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(skip)]
+    path: PathBuf,
+    /// The original path of every renamed file, by its current path.
+    #[serde(default)]
+    original: HashMap<PathBuf, PathBuf>,
+    /// Renames which were planned, but not confirmed to be done.
+    #[serde(default)]
+    pending: Vec<Rename>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Rename {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+impl Journal {
+    /// Opens the journal of the instance, and rolls back the renames of an apply that did not finish.
+    pub fn open(game_dir: &Path) -> Journal {
+        let mut journal = Journal::read(game_dir);
+        if !journal.pending.is_empty() {
+            info!(
+                "Rolling back {} renames which did not finish",
+                journal.pending.len()
+            );
+            for planned in std::mem::take(&mut journal.pending).into_iter().rev() {
+                // Only the renames which actually happened get undone,
+                // the original file names were saved before the apply started.
+                if planned.to.exists() && !planned.from.exists() {
+                    if let Err(error) = rename(&planned.to, &planned.from) {
+                        error!("Failed to roll back {:?}: {error}", planned.to);
+                    }
+                }
+            }
+            journal.save();
+        }
+
+        journal
+    }
+
+    /// Reads the journal of the instance without touching any file.
+    pub fn read(game_dir: &Path) -> Journal {
+        let path = splinter_dir(game_dir).join("journal.json");
+        let mut journal = match read_to_string(&path) {
+            Ok(data) => serde_json::from_str(&data).unwrap_or_else(|error| {
+                warn!("Failed to read rename journal {path:?}: {error}");
+                Journal::default()
+            }),
+            Err(_) => Journal::default(),
+        };
+        journal.path = path;
+        journal
+    }
+
+    /// The path the file had before splinter renamed it.
+    pub fn original<'a>(&'a self, path: &'a Path) -> &'a Path {
+        self.original.get(path).map_or(path, |original| original.as_path())
+    }
+
+    /// Renames the files, returning the result of each rename in the same order.
+    pub fn apply(&mut self, renames: &[Rename]) -> Vec<io::Result<()>> {
+        if renames.is_empty() {
+            return vec![];
+        }
+
+        self.pending = renames.to_vec();
+        self.save();
+
+        let results = renames
+            .iter()
+            .map(|planned| self.rename(&planned.from, &planned.to))
+            .collect();
+
+        self.pending.clear();
+        self.save();
+        results
+    }
+
+    /// Renames every file back to the name it had before splinter touched it.
+    /// Jars which are still temporarily disabled get enabled, even if the journal got lost.
+    pub fn restore(&mut self, mods_dir: &Path) {
+        for (current, original) in std::mem::take(&mut self.original) {
+            if current.exists() && !original.exists() {
+                if let Err(error) = rename(&current, &original) {
+                    error!("Failed to restore {original:?}: {error}");
+                }
+            }
+        }
+
+        if let Ok(dir) = read_dir(mods_dir) {
+            for path in dir.flatten().map(|entry| entry.path()) {
+                if path.extension().is_some_and(|v| v == "tempdisabled") {
+                    let original = path.with_extension("");
+                    if !original.exists() {
+                        if let Err(error) = rename(&path, &original) {
+                            error!("Failed to restore {original:?}: {error}");
+                        }
+                    }
+                }
+            }
+        }
+
+        self.remove();
+    }
+
+    /// Forgets about the original file names, as the user is done with the mods folder.
+    pub fn remove(&mut self) {
+        self.original.clear();
+        self.pending.clear();
+        if self.path.exists() {
+            if let Err(error) = remove_file(&self.path) {
+                warn!("Failed to remove rename journal: {error}");
+            }
+        }
+    }
+
+    fn rename(&mut self, from: &Path, to: &Path) -> io::Result<()> {
+        rename(from, to)?;
+        debug!("Renamed {from:?} to {to:?}");
+
+        let original = self
+            .original
+            .remove(from)
+            .unwrap_or_else(|| from.to_path_buf());
+        if original != to {
+            self.original.insert(to.to_path_buf(), original);
+        }
+        Ok(())
+    }
+
+    fn save(&self) {
+        let result = serde_json::to_vec(self)
+            .map_err(io::Error::from)
+            .and_then(|data| write_file(&self.path, &data));
+        if let Err(error) = result {
+            error!("Failed to write rename journal: {error}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, write};
+
+    use crate::modpack::journal::{Journal, Rename};
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn restore() {
+        let game_dir = TempDir::new("journal-restore");
+        let mods = game_dir.join("mods");
+        create_dir_all(&mods).unwrap();
+        for name in ["a.jar", "b.jar", "c.jar.disabled", "lost.jar.tempdisabled"] {
+            write(mods.join(name), []).unwrap();
+        }
+
+        let mut journal = Journal::open(&game_dir);
+        let results = journal.apply(&[
+            Rename {
+                from: mods.join("a.jar"),
+                to: mods.join("a.jar.tempdisabled"),
+            },
+            Rename {
+                from: mods.join("c.jar.disabled"),
+                to: mods.join("c.jar"),
+            },
+            Rename {
+                from: mods.join("missing.jar"),
+                to: mods.join("missing.jar.tempdisabled"),
+            },
+        ]);
+        assert!(results[0].is_ok() && results[1].is_ok() && results[2].is_err());
+
+        // A crash in the middle of the next apply.
+        let mut journal = Journal::open(&game_dir);
+        journal.pending = vec![Rename {
+            from: mods.join("b.jar"),
+            to: mods.join("b.jar.tempdisabled"),
+        }];
+        journal.save();
+        std::fs::rename(mods.join("b.jar"), mods.join("b.jar.tempdisabled")).unwrap();
+
+        let mut journal = Journal::open(&game_dir);
+        assert!(mods.join("b.jar").exists());
+        journal.restore(&mods);
+
+        let mut files: Vec<String> = std::fs::read_dir(&mods)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["a.jar", "b.jar", "c.jar.disabled", "lost.jar"]);
+    }
+}
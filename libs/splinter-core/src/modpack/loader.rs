@@ -0,0 +1,134 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io;
+use std::panic::catch_unwind;
+use std::path::PathBuf;
+
+use crossbeam::channel::{Receiver, unbounded};
+use tracing::{debug, error};
+
+use crate::{Dependency, NotificationEvent, Platform, Plugin, PluginList, PluginMetadata, Severity};
+
+pub struct ModpackLoader {
+    total_plugins: usize,
+    receiver: Receiver<(PathBuf, io::Result<Option<Plugin>>)>,
+}
+
+impl ModpackLoader {
+    pub fn new(paths: Vec<PathBuf>) -> ModpackLoader {
+        let (sender, receiver) = unbounded();
+        let total_plugins = paths.len();
+        for path in paths {
+            let sender = sender.clone();
+            std::thread::spawn(move || {
+                // Every file has to be answered for, or the loading never finishes.
+                let plugin = catch_unwind(|| Plugin::new(path.clone()))
+                    .unwrap_or_else(|_| Err(io::Error::other("reading it crashed")));
+                sender.send((path, plugin)).unwrap();
+            });
+        }
+
+        ModpackLoader {
+            total_plugins,
+            receiver,
+        }
+    }
+
+    /// Adds the plugins which finished loading, returns how far the loading is or `None` once every plugin is added.
+    pub fn tick(
+        &mut self,
+        plugins: &mut PluginList,
+        platform: &Platform,
+        notifications: &mut Vec<NotificationEvent>,
+    ) -> Option<f32> {
+        while let Ok((path, plugin)) = self.receiver.try_recv() {
+            if let Ok(Some(plugin)) = plugin {
+                let idx = plugins.list.len();
+
+                let mut add_id = |id: String| {
+                    debug!("Adding id binding {id} to {}", plugin.metadata.id);
+                    if let Some(old) = plugins.lookup.insert(id.clone(), idx) {
+                        notifications.push(NotificationEvent {
+                            title: "Duplicate ids".to_string(),
+                            description: format!(
+                                "Mod \"{}\" and \"{}\" have the same id \"{}\"",
+                                plugin.metadata.name, plugins.list[old].metadata.name, id
+                            ),
+                            ty: Severity::Warning,
+                        });
+                    }
+                };
+
+                add_id(plugin.metadata.id.clone());
+                for id in &plugin.metadata.provides {
+                    add_id(id.clone());
+                }
+
+                plugins.list.push(plugin);
+            } else {
+                if let Err(error) = plugin {
+                    error!("Failed to load {path:?}: {error}");
+                    notifications.push(NotificationEvent {
+                        title: "Failed to load mod".to_string(),
+                        description: format!(
+                            "{:?} could not be read and is left out of the search: {error}",
+                            path.file_name().unwrap_or_default()
+                        ),
+                        ty: Severity::Warning,
+                    });
+                }
+                self.total_plugins -= 1;
+            }
+        }
+
+        if plugins.list.len() >= self.total_plugins {
+            for (id, plugin) in plugins.list.iter().enumerate() {
+                Self::add_modules(id, &plugin.metadata, &mut plugins.lookup);
+            }
+
+            for (plugin, dependency, installed) in plugins.unsatisfied_dependencies(platform) {
+                notifications.push(NotificationEvent {
+                    title: "Unsatisfied dependency".to_string(),
+                    description: describe_unsatisfied(plugin, dependency, installed.as_deref()),
+                    ty: Severity::Warning,
+                });
+            }
+
+            None
+        } else {
+            Some(plugins.list.len() as f32 / self.total_plugins as f32)
+        }
+    }
+
+    fn add_modules(id: usize, plugin: &PluginMetadata, lookup: &mut HashMap<String, usize>) {
+        for metadata in &plugin.contains {
+            Self::add_modules(id, metadata, lookup);
+            match lookup.entry(metadata.id.clone()) {
+                Entry::Occupied(_) => {}
+                Entry::Vacant(entry) => {
+                    entry.insert(id);
+                }
+            }
+        }
+    }
+}
+
+/// Describes why a dependency is not satisfied, for the user.
+pub fn describe_unsatisfied(plugin: &Plugin, dependency: &Dependency, installed: Option<&str>) -> String {
+    let required = if dependency.version.is_any() {
+        format!("\"{}\"", dependency.id)
+    } else {
+        format!("\"{}\" {}", dependency.id, dependency.version)
+    };
+
+    match installed {
+        Some(installed) => format!(
+            "Mod \"{}\" requires {required}, but {installed} is installed",
+            plugin.metadata.name
+        ),
+        None => format!(
+            "Mod \"{}\" requires {required}, which is not installed",
+            plugin.metadata.name
+        ),
+    }
+}
@@ -0,0 +1,124 @@
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::modpack::Platform;
+use crate::providers;
+
+/// How the launcher shows the instance.
+pub struct ModpackMetadata {
+    pub name: String,
+    /// The icon the launcher shows for the instance, if it is a file.
+    pub icon: Option<PathBuf>,
+}
+
+impl ModpackMetadata {
+    pub fn new(game_dir: &Path) -> ModpackMetadata {
+        let (name, icon) = Self::new_multimc(game_dir)
+            .or_else(|| Self::new_modrinth(game_dir))
+            .or_else(|| Self::new_curseforge(game_dir))
+            .or_else(|| Self::new_atlauncher(game_dir))
+            .unwrap_or((None, None));
+
+        let name = name.unwrap_or_else(|| {
+            let dir = game_dir.file_name().unwrap_or_default().to_string_lossy();
+            match dir.as_ref() {
+                ".minecraft" | "minecraft" => "Minecraft".to_string(),
+                _ => dir.to_string(),
+            }
+        });
+        ModpackMetadata { name, icon }
+    }
+
+    /// Names the instance along with what it runs on, like `Pack · Fabric 0.15.11 · 1.20.1`.
+    pub fn title(&self, platform: &Platform) -> String {
+        let mut parts = vec![self.name.clone()];
+        parts.extend(platform.loader());
+        parts.extend(platform.minecraft().map(str::to_string));
+        parts.join(" · ")
+    }
+
+    /// MultiMC and Prism Launcher keep the `instance.cfg` next to the game directory,
+    /// the icon is in the icons of the launcher, named by its key.
+    fn new_multimc(game_dir: &Path) -> Option<(Option<String>, Option<PathBuf>)> {
+        let instance = game_dir.parent()?;
+        let config = read_to_string(instance.join("instance.cfg")).ok()?;
+        let value = |key: &str| {
+            config.lines().find_map(|line| {
+                let (name, value) = line.split_once('=')?;
+                (name.trim() == key && !value.trim().is_empty()).then(|| value.trim().to_string())
+            })
+        };
+
+        // The icons are next to the instances, unless the instances were moved.
+        let data_dirs = instance
+            .parent()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .into_iter()
+            .chain(
+                providers()
+                    .into_iter()
+                    .flat_map(|provider| provider.data_dirs()),
+            );
+        let icon = value("iconKey").and_then(|key| {
+            data_dirs
+                .flat_map(|dir| {
+                    ["png", "jpg", "jpeg", "gif", "ico"]
+                        .map(|extension| dir.join("icons").join(format!("{key}.{extension}")))
+                })
+                .find(|path| path.is_file())
+        });
+        Some((value("name"), icon))
+    }
+
+    /// The Modrinth App keeps a `profile.json` in the profile, which points to the icon.
+    fn new_modrinth(game_dir: &Path) -> Option<(Option<String>, Option<PathBuf>)> {
+        let profile = read_json(&game_dir.join("profile.json"))?;
+        let metadata = &profile["metadata"];
+        let icon = metadata["icon"].as_str().or(profile["icon_path"].as_str());
+        Some((
+            metadata["name"].as_str().map(str::to_string),
+            icon.map(PathBuf::from),
+        ))
+    }
+
+    /// The CurseForge app only links the icon online, so there is none to show.
+    fn new_curseforge(game_dir: &Path) -> Option<(Option<String>, Option<PathBuf>)> {
+        let instance = read_json(&game_dir.join("minecraftinstance.json"))?;
+        Some((instance["name"].as_str().map(str::to_string), None))
+    }
+
+    fn new_atlauncher(game_dir: &Path) -> Option<(Option<String>, Option<PathBuf>)> {
+        let instance = read_json(&game_dir.join("instance.json"))?;
+        let name = instance["launcher"]["name"].as_str().map(str::to_string);
+        let icon = game_dir.join("instance.png");
+        Some((name, icon.is_file().then_some(icon)))
+    }
+}
+
+fn read_json(path: &Path) -> Option<Value> {
+    serde_json::from_str(&read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::create_dir_all;
+
+    use crate::modpack::metadata::ModpackMetadata;
+    use crate::test_util::{write_file, TempDir};
+
+    #[test]
+    fn multimc() {
+        let data_dir = TempDir::new("metadata-multimc");
+        let instance = data_dir.join("instances/pack");
+        create_dir_all(instance.join("minecraft")).unwrap();
+        write_file(&instance.join("instance.cfg"), "iconKey=pack_icon\nname=My Pack\n");
+        write_file(&data_dir.join("icons/pack_icon.png"), []);
+
+        let (name, icon) = ModpackMetadata::new_multimc(&instance.join("minecraft")).unwrap();
+        assert_eq!(name.as_deref(), Some("My Pack"));
+        assert_eq!(icon, Some(data_dir.join("icons/pack_icon.png")));
+    }
+}
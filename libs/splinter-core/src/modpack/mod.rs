@@ -0,0 +1,1030 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{create_dir_all, read_dir, rename, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, info, warn};
+
+pub use impact::ForceImpact;
+pub use journal::{Journal, Rename};
+pub use loader::{describe_unsatisfied, ModpackLoader};
+pub use metadata::ModpackMetadata;
+pub use oracle::{Oracle, OracleOutput, OracleRun};
+pub use platform::{Platform, PlatformStatus};
+pub use search::{Estimate, SearchConfig, SearchMode, SearchStrategy};
+pub use session::Session;
+
+use crate::{Dependency, NotificationEvent, Plugin, PluginStatus, Severity};
+
+mod impact;
+mod journal;
+mod loader;
+mod metadata;
+mod oracle;
+mod platform;
+mod search;
+mod session;
+mod stability;
+
+/// An instance being searched, it renames the jars in its mods folder to the statuses of the plugins.
+pub struct Modpack {
+    path: PathBuf,
+    metadata: ModpackMetadata,
+    platform: Platform,
+    plugins: PluginList,
+    loader: Option<ModpackLoader>,
+    journal: Journal,
+    // The session from a previous run, which gets applied once the plugins are loaded.
+    session: Option<Session>,
+
+    // The mods which got enabled because an enabled mod depends on them, with the mod depending on them.
+    enabled_by: HashMap<String, String>,
+
+    mode: SearchMode,
+    strategy: Box<dyn SearchStrategy>,
+    search_config: SearchConfig,
+    // How many tests are left, not known while loading.
+    estimate: Option<Estimate>,
+    undo_queue: Vec<State>,
+    undo_queue_location: usize,
+    // The status and forced status of every plugin, as they are on disk.
+    applied: Vec<(PluginStatus, Option<bool>)>,
+    // Notifications for the user, until they get taken.
+    notifications: Vec<NotificationEvent>,
+    // Opened with [`Modpack::inspect`], the files are never changed.
+    read_only: bool,
+}
+
+impl Modpack {
+    pub fn new(path: PathBuf) -> Option<Modpack> {
+        Self::open(path, false)
+    }
+
+    /// Loads the instance as it is on disk, to look at the search without continuing it.
+    /// Nothing gets renamed or written, neither to start a session nor to roll back an unfinished one.
+    pub fn inspect(path: PathBuf) -> Option<Modpack> {
+        Self::open(path, true)
+    }
+
+    fn open(path: PathBuf, read_only: bool) -> Option<Modpack> {
+        let path = Self::game_dir(path)?;
+
+        info!("Loading {path:?}");
+        // Has to happen before the plugins get read, so they see the files as they were.
+        let journal = match read_only {
+            true => Journal::read(&path),
+            false => Journal::open(&path),
+        };
+        if let Ok(dir) = read_dir(path.join("mods")) {
+            let session = Session::load(&path);
+            if let Some(session) = &session {
+                info!("Recovering session at step {}/{}", session.step(), session.steps());
+            }
+
+            return Some(Modpack {
+                metadata: ModpackMetadata::new(&path),
+                platform: Platform::new(&path),
+                path,
+                journal,
+                session,
+                plugins: PluginList::new(),
+                loader: Some(ModpackLoader::new(dir.flatten().map(|v| v.path()).collect())),
+                enabled_by: HashMap::new(),
+                mode: SearchMode::Bisect,
+                strategy: SearchMode::Bisect.strategy(),
+                search_config: SearchConfig::default(),
+                estimate: None,
+                undo_queue: vec![],
+                undo_queue_location: 0,
+                applied: vec![],
+                notifications: vec![],
+                read_only,
+            });
+        } else {
+            warn!("Could not find mods folder")
+        }
+
+        None
+    }
+
+    /// Finds the directory containing the mods folder, from either the instance, the game or the mods directory.
+    pub fn game_dir(mut path: PathBuf) -> Option<PathBuf> {
+        if let Ok(new_path) = path.strip_prefix("~") {
+            path = dirs::home_dir()?.join(new_path);
+        }
+        if path.ends_with("mods") {
+            path = path.parent()?.to_path_buf();
+        }
+        let mc = path.join(".minecraft");
+        if mc.exists() {
+            path = mc;
+        }
+        Some(path)
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Adds the plugins which finished loading, and continues the search once every plugin is loaded.
+    /// Returns how far the loading is, `None` once it is done.
+    pub fn tick_loader(&mut self) -> Option<f32> {
+        let loader = self.loader.as_mut()?;
+        let progress = loader.tick(&mut self.plugins, &self.platform, &mut self.notifications);
+        if progress.is_none() {
+            self.loader = None;
+            self.applied = self.plugins.list.iter().map(|v| (v.status, v.forced_status)).collect();
+            self.update_stability();
+            match self.session.take() {
+                Some(session) => self.recover_session(session),
+                None if self.read_only => {}
+                None => self.start_session(),
+            }
+            self.update_estimate();
+        }
+        progress
+    }
+
+    /// Forces a plugin on or off, `None` lets the search decide again. Returns `false` if there is no such plugin.
+    pub fn set_forced(&mut self, id: &str, forced: Option<bool>) -> bool {
+        let Some(plugin) = self.plugins.get_mut(id) else {
+            return false;
+        };
+        plugin.forced_status = forced;
+        let id = plugin.metadata.id.clone();
+        self.apply_forced(&[id]);
+        true
+    }
+
+    /// Applies the forced status of the plugins which got changed through [`Modpack::plugins_mut`].
+    pub fn apply_forced(&mut self, forced: &[String]) {
+        if self.push_changes() {
+            self.save_session();
+        }
+        self.update_estimate();
+
+        // Warn if forcing a plugin made it load together with a mod it breaks.
+        for (plugin, broken) in self.plugins.breaking_pairs() {
+            if forced.contains(&plugin.metadata.id) || forced.contains(&broken.metadata.id) {
+                self.notifications.push(NotificationEvent {
+                    title: "Incompatible mods".to_string(),
+                    description: format!(
+                        "Mod \"{}\" is incompatible with \"{}\", the game will likely not start",
+                        plugin.metadata.name, broken.metadata.name
+                    ),
+                    ty: Severity::Warning,
+                });
+            }
+        }
+    }
+
+    /// Continues the search after the issue did not show up. Wont do anything if the modpack is currently loading.
+    pub fn invert(&mut self) {
+        self.verdict(false);
+    }
+
+    /// Continues the search after the issue showed up. Wont do anything if the modpack is currently loading.
+    pub fn split(&mut self) {
+        self.verdict(true);
+    }
+
+    /// Goes through the enabled mods and enables their dependencies.
+    /// It does this recursively because dependencies may depend on other dependencies.
+    fn enable_dependencies(&mut self) {
+        self.enabled_by.clear();
+        let mut scan = true;
+        while scan {
+            scan = false;
+            for (id, dependant) in self.get_dependant_disabled_mods() {
+                if let Some(value) = self.plugins.get(&id) {
+                    if value.forced_status == Some(false) {
+                        // We ask the user to enable this mod at the end of the split operations,
+                        // as they have disabled it and it's about to break things.
+                        continue;
+                    }
+                }
+
+                let plugin = self.plugins.get_mut(&id).unwrap();
+                plugin.status = PluginStatus::Enabled;
+                self.enabled_by.insert(plugin.metadata.id.clone(), dependant);
+
+                // We just enabled a dependency,
+                // we need to run this again as there may be new dependencies which need to be enabled.
+                scan = true;
+            }
+        }
+    }
+
+    /// Disables one side of every pair of enabled mods which break each other,
+    /// along with the mods depending on it, as the loader would refuse to start otherwise.
+    fn disable_breaking(&mut self) {
+        let pairs: Vec<(String, String)> = self
+            .plugins
+            .breaking_pairs()
+            .into_iter()
+            .map(|(v0, v1)| (v0.metadata.id.clone(), v1.metadata.id.clone()))
+            .collect();
+
+        for (v0, v1) in pairs {
+            let both_enabled = [&v0, &v1]
+                .iter()
+                .all(|id| self.plugins.get(id).is_some_and(|plugin| plugin.enabled()));
+            if !both_enabled {
+                // A previous pair already resolved this one.
+                continue;
+            }
+
+            // We disable the one which takes the least amount of mods down with it.
+            let victim = [v0.clone(), v1.clone()]
+                .into_iter()
+                .filter(|id| self.plugins.get(id).unwrap().forced_status.is_none())
+                .min_by_key(|id| self.find_dependants(id).len());
+
+            match victim {
+                Some(victim) => {
+                    debug!("Disabling {victim} because \"{v0}\" breaks \"{v1}\"");
+                    self.disable_with_dependants(&victim);
+                }
+                None => warn!("Mod {v0} breaks {v1}, but both are forced on"),
+            }
+        }
+    }
+
+    /// Disables a plugin and every enabled plugin that depends on it.
+    fn disable_with_dependants(&mut self, id: &str) {
+        let Some(plugin) = self.plugins.get_mut(id) else {
+            return;
+        };
+        if plugin.forced_status.is_some() || plugin.status != PluginStatus::Enabled {
+            return;
+        }
+        plugin.status = PluginStatus::Disabled;
+
+        let mut ids = vec![plugin.metadata.id.clone()];
+        ids.extend(plugin.metadata.provides.iter().cloned());
+        for id in ids {
+            for dependant in self.find_dependants(&id) {
+                self.disable_with_dependants(&dependant);
+            }
+        }
+    }
+
+    /// Deals with a disabled mod which enabled mods depend on, see [`Modpack::get_dependant_disabled_mods`].
+    pub fn resolve(&mut self, id: &str, resolution: Resolution) {
+        match resolution {
+            Resolution::EnableAnyway => {
+                if let Some(plugin) = self.plugins.get_mut(id) {
+                    if plugin.forced_status.is_some() {
+                        plugin.forced_status = Some(true);
+                    }
+                    plugin.status = PluginStatus::Enabled;
+                }
+            }
+            Resolution::DisableDependants => {
+                for dependant in self.find_dependants(id) {
+                    // Forcing a dependant on would keep it loaded, so the force has to go.
+                    if let Some(plugin) = self.plugins.get_mut(&dependant) {
+                        if plugin.forced_status == Some(true) {
+                            plugin.forced_status = None;
+                        }
+                    }
+                    self.disable_with_dependants(&dependant);
+                }
+            }
+            Resolution::RemoveForce => {
+                if let Some(plugin) = self.plugins.get_mut(id) {
+                    plugin.forced_status = None;
+                }
+                self.enable_dependencies();
+            }
+        }
+
+        if self.push_changes() {
+            self.save_state();
+            self.save_session();
+        } else {
+            self.reset_search();
+        }
+        self.update_estimate();
+    }
+
+    /// Get disabled mods which are depended by other enabled mods.
+    ///
+    /// Basically we get the mods which the modloader is about to tell the user are not there,
+    /// along with one of the mods depending on them.
+    pub fn get_dependant_disabled_mods(&self) -> HashMap<String, String> {
+        let mut to_enable = HashMap::new();
+
+        for plugin in self.plugins.iter() {
+            if plugin.status == PluginStatus::Enabled {
+                for dependency in &plugin.metadata.depends_on {
+                    if let Some(value) = self.plugins.get(&dependency.id) {
+                        // Enabling a mod with the wrong version would not fix anything,
+                        // those get reported once the modpack is loaded.
+                        if !value.status.enabled() && self.plugins.satisfies(dependency) == Some(true) {
+                            to_enable.insert(dependency.id.clone(), plugin.metadata.id.clone());
+                        }
+                    }
+                }
+            }
+        }
+
+        to_enable
+    }
+
+    /// Gives a list of mods which are dependant on this id.
+    pub fn find_dependants(&self, id: &str) -> Vec<String> {
+        let mut dependants = Vec::new();
+        for plugin in self.plugins.iter() {
+            if plugin.status == PluginStatus::Enabled {
+                for depends_on in &plugin.metadata.depends_on {
+                    if depends_on.id == id {
+                        dependants.push(plugin.metadata.id.clone());
+                    }
+                }
+            }
+        }
+
+        dependants
+    }
+
+    pub fn plugins(&self) -> &PluginList {
+        &self.plugins
+    }
+
+    pub fn plugins_mut(&mut self) -> &mut PluginList {
+        &mut self.plugins
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loader.is_some()
+    }
+
+    pub fn metadata(&self) -> &ModpackMetadata {
+        &self.metadata
+    }
+
+    pub fn platform(&self) -> &Platform {
+        &self.platform
+    }
+
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// The name of the instance, with what it runs on.
+    pub fn title(&self) -> String {
+        self.metadata.title(&self.platform)
+    }
+
+    /// The mods which got enabled because an enabled mod depends on them, with the mod depending on them.
+    pub fn enabled_by(&self) -> &HashMap<String, String> {
+        &self.enabled_by
+    }
+
+    pub fn mode(&self) -> SearchMode {
+        self.mode
+    }
+
+    /// The step of the undo queue the user is at, counting from 1.
+    pub fn step(&self) -> usize {
+        self.undo_queue_location + 1
+    }
+
+    pub fn steps(&self) -> usize {
+        self.undo_queue.len()
+    }
+
+    /// Every step of the search, with the status every plugin had.
+    pub fn states(&self) -> &[State] {
+        &self.undo_queue
+    }
+
+    pub fn estimate(&self) -> Option<Estimate> {
+        self.estimate
+    }
+
+    /// A short text on how far the search is, if the search mode has one.
+    pub fn progress(&self) -> Option<String> {
+        self.strategy.progress(&self.plugins)
+    }
+
+    /// Takes the notifications which were not shown yet.
+    pub fn take_notifications(&mut self) -> Vec<NotificationEvent> {
+        std::mem::take(&mut self.notifications)
+    }
+
+    pub fn save_state(&mut self) {
+        if self.is_loading() {
+            return;
+        }
+
+        if self.undo_queue_location < self.undo_queue.len().saturating_sub(1) {
+            let to_remove = self.undo_queue.len().saturating_sub(1) - self.undo_queue_location;
+            for _ in 0..to_remove {
+                self.undo_queue.pop();
+            }
+        }
+
+        let mut state = HashMap::new();
+        for plugin in self.plugins().iter() {
+            state.insert(plugin.metadata.id.clone(), plugin.status);
+        }
+
+        self.undo_queue_location = self.undo_queue.len();
+        self.undo_queue.push(State {
+            plugins: state,
+            search: self.strategy.save(),
+            verdict: None,
+            enabled_by: self.enabled_by.clone(),
+        });
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.undo_queue_location > 0
+    }
+
+    pub fn undo(&mut self) {
+        if self.is_loading() {
+            return;
+        }
+        if self.can_undo() {
+            self.undo_queue_location -= 1;
+            self.update_state();
+            if self.push_changes() {
+                self.save_session();
+            } else {
+                self.undo_queue_location += 1;
+                self.reset_search();
+            }
+            self.update_estimate();
+        }
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.undo_queue_location < self.undo_queue.len().saturating_sub(1)
+    }
+
+    pub fn redo(&mut self) {
+        if self.is_loading() {
+            return;
+        }
+        if self.can_redo() {
+            self.undo_queue_location += 1;
+            self.update_state();
+            if self.push_changes() {
+                self.save_session();
+            } else {
+                self.undo_queue_location -= 1;
+                self.reset_search();
+            }
+            self.update_estimate();
+        }
+    }
+
+    /// Renames the files to match the status of the plugins, either every file gets renamed or none.
+    ///
+    /// If a rename fails, the renames which already happened are rolled back
+    /// and the plugins get the status they have on disk again, so the user never tests something else than shown.
+    fn push_changes(&mut self) -> bool {
+        if self.read_only {
+            warn!("Not renaming the mods of {:?}, it was opened read-only", self.path);
+            return false;
+        }
+
+        let mut renames = Vec::new();
+        let mut moved = Vec::new();
+        for (i, plugin) in self.plugins.list.iter().enumerate() {
+            if let Some(to) = plugin.target_path() {
+                if to != plugin.path() {
+                    renames.push(Rename {
+                        from: plugin.path().to_path_buf(),
+                        to,
+                    });
+                    moved.push(i);
+                }
+            }
+        }
+
+        let results = self.journal.apply(&renames);
+        let mut done = Vec::new();
+        let mut failed = Vec::new();
+        for ((i, rename), result) in moved.into_iter().zip(renames).zip(results) {
+            match result {
+                Ok(()) => done.push((i, rename)),
+                Err(error) => {
+                    error!("Failed to rename {:?}: {error}", rename.from);
+                    failed.push(format!("{:?}: {error}", rename.from.file_name().unwrap_or_default()));
+                }
+            }
+        }
+
+        if failed.is_empty() {
+            for (i, rename) in done {
+                self.plugins.list[i].set_path(rename.to);
+            }
+            self.applied = self.plugins.list.iter().map(|v| (v.status, v.forced_status)).collect();
+            return true;
+        }
+
+        let rollback: Vec<Rename> = done
+            .iter()
+            .rev()
+            .map(|(_, rename)| Rename {
+                from: rename.to.clone(),
+                to: rename.from.clone(),
+            })
+            .collect();
+        // The plugins whose file could not be renamed back, with where the file is now.
+        let mut stuck = HashMap::new();
+        let mut stuck_names = Vec::new();
+        for (rename, result) in rollback.iter().zip(self.journal.apply(&rollback)) {
+            if let Err(error) = result {
+                error!("Failed to roll back {:?}: {error}", rename.from);
+                stuck_names.push(format!("{:?}: {error}", rename.from.file_name().unwrap_or_default()));
+                if let Some((i, _)) = done.iter().find(|(_, v)| v.to == rename.from) {
+                    stuck.insert(*i, rename.from.clone());
+                }
+            }
+        }
+
+        for (i, (plugin, applied)) in self.plugins.list.iter_mut().zip(&mut self.applied).enumerate() {
+            match stuck.remove(&i) {
+                // The file keeps its new name, so the plugin keeps the status that name stands for.
+                Some(path) => {
+                    plugin.set_path(path);
+                    *applied = (plugin.status, plugin.forced_status);
+                }
+                None => (plugin.status, plugin.forced_status) = *applied,
+            }
+        }
+
+        let notification = if stuck_names.is_empty() {
+            NotificationEvent {
+                title: "Failed to apply changes".to_string(),
+                description: format!(
+                    "Nothing was changed, as {} mods could not be renamed. Is the game still running?\n{}",
+                    failed.len(),
+                    failed.join("\n")
+                ),
+                ty: Severity::Error,
+            }
+        } else {
+            NotificationEvent {
+                title: "Failed to roll back changes".to_string(),
+                description: format!(
+                    "{} mods could not be renamed, and these mods could not be renamed back. \
+                     They were left in place with their new status:\n{}",
+                    failed.len(),
+                    stuck_names.join("\n")
+                ),
+                ty: Severity::Error,
+            }
+        };
+        self.notifications.push(notification);
+        false
+    }
+
+    /// Writes the search so far to the instance, so it can be continued after a restart.
+    pub fn save_session(&self) {
+        if self.is_loading() || self.read_only {
+            return;
+        }
+
+        let session = Session {
+            mode: self.mode,
+            config: self.search_config,
+            undo_queue: self.undo_queue.clone(),
+            undo_queue_location: self.undo_queue_location,
+            forced: self
+                .plugins
+                .list
+                .iter()
+                .filter_map(|plugin| Some((plugin.metadata.id.clone(), plugin.forced_status?)))
+                .collect(),
+            stability_overrides: self
+                .plugins
+                .list
+                .iter()
+                .filter_map(|plugin| Some((plugin.metadata.id.clone(), plugin.stability_override?)))
+                .collect(),
+        };
+        if let Err(error) = session.save(&self.path) {
+            warn!("Failed to save session: {error}");
+        }
+    }
+
+    /// Starts the search with every mod which is not forced enabled,
+    /// including jars left disabled by a search whose session got lost.
+    fn start_session(&mut self) {
+        for plugin in self.plugins.iter_mut() {
+            if plugin.forced_status.is_none() {
+                plugin.status = PluginStatus::Enabled;
+            }
+        }
+        self.push_changes();
+        self.save_state();
+    }
+
+    /// Continues the search where the session left off.
+    fn recover_session(&mut self, session: Session) {
+        for plugin in self.plugins.iter_mut() {
+            let id = &plugin.metadata.id;
+            if let Some(forced) = session.forced.get(id) {
+                plugin.forced_status = Some(*forced);
+            }
+            if let Some(stability) = session.stability_overrides.get(id) {
+                plugin.stability_override = Some(*stability);
+            }
+        }
+
+        self.mode = session.mode;
+        self.strategy = session.mode.strategy();
+        self.search_config = session.config;
+        self.undo_queue_location = session.step() - 1;
+        self.undo_queue = session.undo_queue;
+        if self.read_only {
+            // The plugins keep the status of their file.
+            self.reset_search();
+            return;
+        }
+        self.update_state();
+        if !self.push_changes() {
+            // The session stays on disk, so it can be recovered once the files are no longer in use.
+            self.strategy = self.mode.strategy();
+            self.enabled_by.clear();
+            self.undo_queue.clear();
+            self.save_state();
+        }
+    }
+
+    /// Ends the search, every mod which is not forced gets enabled again.
+    pub fn end_session(&mut self) {
+        for plugin in self.plugins.iter_mut() {
+            if plugin.forced_status.is_none() {
+                plugin.status = PluginStatus::Enabled;
+            }
+        }
+        // If the files could not be renamed, the session is kept to recover or restore it later.
+        if self.push_changes() {
+            Session::remove(&self.path);
+            self.journal.remove();
+        }
+    }
+
+    /// Ends the search, and gives every jar back the file name it had before the session started.
+    pub fn restore(&mut self) {
+        info!("Restoring the original mods folder");
+        self.journal.restore(&self.path.join("mods"));
+        Session::remove(&self.path);
+    }
+
+    /// Checks for a session to recover in the instance.
+    pub fn session(path: &Path) -> Option<Session> {
+        Session::load(&Self::game_dir(path.to_path_buf())?)
+    }
+
+    fn update_state(&mut self) {
+        self.reset_search();
+        let state = &self.undo_queue[self.undo_queue_location];
+        for (id, status) in &state.plugins {
+            if let Some(plugin) = self.plugins.get_mut(id) {
+                plugin.status = *status;
+            } else {
+                warn!("Plugin {id} does not exist, but is referenced in state");
+            }
+        }
+    }
+
+    /// Takes the search and the enabled dependencies back to the current step,
+    /// as a change which could not be applied already went through them.
+    fn reset_search(&mut self) {
+        let state = &self.undo_queue[self.undo_queue_location];
+        self.strategy.load(&state.search);
+        self.enabled_by = state.enabled_by.clone();
+    }
+}
+
+/// What to do about a disabled mod which enabled mods depend on.
+#[derive(Copy, Clone)]
+pub enum Resolution {
+    EnableAnyway,
+    /// Keeps it disabled, and disables the mods needing it.
+    DisableDependants,
+    /// Lets the search decide on it again, which enables it.
+    RemoveForce,
+}
+
+/// Adds the dependencies of the set, and their dependencies.
+fn closure(set: &[String], dependencies: impl Fn(&str) -> Vec<String>) -> Vec<String> {
+    let mut closure = set.to_vec();
+    let mut seen: HashSet<String> = set.iter().cloned().collect();
+    let mut i = 0;
+    while i < closure.len() {
+        for id in dependencies(&closure[i]) {
+            if seen.insert(id.clone()) {
+                closure.push(id);
+            }
+        }
+        i += 1;
+    }
+
+    closure
+}
+
+/// Where splinter keeps its files for an instance.
+fn splinter_dir(game_dir: &Path) -> PathBuf {
+    game_dir.join(".splinter")
+}
+
+/// Writes next to the file first, so a crash while writing does not lose the old contents.
+fn write_file(path: &Path, data: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+    }
+
+    let temp = path.with_extension("tmp");
+    write(&temp, data)?;
+    rename(temp, path)
+}
+
+#[derive(Default)]
+pub struct PluginList {
+    list: Vec<Plugin>,
+    lookup: HashMap<String, usize>,
+}
+
+impl PluginList {
+    pub fn new() -> PluginList {
+        PluginList {
+            list: vec![],
+            lookup: Default::default(),
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.lookup.contains_key(id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Plugin> {
+        self.list.get(*self.lookup.get(id)?)
+    }
+
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Plugin> {
+        self.list.get_mut(*self.lookup.get(id)?)
+    }
+
+    /// The mods which have to be enabled for the mod to load, and which splitting is allowed to enable.
+    pub fn dependencies(&self, id: &str) -> Vec<String> {
+        let Some(plugin) = self.get(id) else {
+            return vec![];
+        };
+
+        plugin
+            .metadata
+            .depends_on
+            .iter()
+            .filter(|dependency| self.satisfies(dependency) == Some(true))
+            .filter_map(|dependency| self.get(&dependency.id))
+            .filter(|value| value.forced_status.is_none() && value.metadata.id != plugin.metadata.id)
+            .map(|value| value.metadata.id.clone())
+            .collect()
+    }
+
+    /// Adds the mods which get enabled along with the set, because the set needs them.
+    pub fn dependency_closure(&self, set: &[String]) -> Vec<String> {
+        closure(set, |id| self.dependencies(id))
+    }
+
+    /// Checks if the installed plugin has a version within the range of the dependency.
+    /// Returns `None` if nothing provides the dependency.
+    pub fn satisfies(&self, dependency: &Dependency) -> Option<bool> {
+        let version = self.get(&dependency.id)?.metadata.module_version(&dependency.id)?;
+        Some(dependency.version.matches(version))
+    }
+
+    /// Finds the dependencies which are not installed, or not in a version that the dependant accepts,
+    /// together with the version that is installed.
+    /// Those are going to break no matter how we split.
+    pub fn unsatisfied_dependencies<'a>(
+        &'a self,
+        platform: &Platform,
+    ) -> Vec<(&'a Plugin, &'a Dependency, Option<String>)> {
+        let mut unsatisfied = Vec::new();
+        for plugin in self.iter() {
+            if plugin.forced_status == Some(false) {
+                continue;
+            }
+
+            for dependency in &plugin.metadata.depends_on {
+                match self.satisfies(dependency) {
+                    Some(true) => {}
+                    Some(false) => {
+                        let installed = self
+                            .get(&dependency.id)
+                            .and_then(|value| value.metadata.module_version(&dependency.id));
+                        unsatisfied.push((plugin, dependency, installed.map(str::to_string)));
+                    }
+                    None => match platform.check(dependency) {
+                        Some(PlatformStatus::Satisfied | PlatformStatus::Unknown) => {}
+                        Some(PlatformStatus::Mismatch(installed)) => {
+                            unsatisfied.push((plugin, dependency, Some(installed)));
+                        }
+                        Some(PlatformStatus::Missing) | None => {
+                            unsatisfied.push((plugin, dependency, None));
+                        }
+                    },
+                }
+            }
+        }
+
+        unsatisfied
+    }
+
+    /// Finds pairs of plugins which are both going to be loaded, while the first one breaks the second.
+    pub fn breaking_pairs(&self) -> Vec<(&Plugin, &Plugin)> {
+        let mut pairs = Vec::new();
+        for (i, plugin) in self.list.iter().enumerate() {
+            if !plugin.enabled() {
+                continue;
+            }
+
+            for breaks in &plugin.metadata.breaks {
+                let Some(&other) = self.lookup.get(&breaks.id) else {
+                    continue;
+                };
+                let broken = &self.list[other];
+                let matches = broken
+                    .metadata
+                    .module_version(&breaks.id)
+                    .is_some_and(|version| breaks.version.matches(version));
+                if other != i && broken.enabled() && matches {
+                    pairs.push((plugin, broken));
+                }
+            }
+        }
+
+        pairs
+    }
+
+    pub fn iter(&self) -> &[Plugin] {
+        &self.list
+    }
+
+    pub fn iter_mut(&mut self) -> &mut [Plugin] {
+        &mut self.list
+    }
+}
+
+/// A step of the search.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct State {
+    pub plugins: HashMap<String, PluginStatus>,
+    /// The state of the search strategy.
+    #[serde(default)]
+    search: serde_json::Value,
+    /// What the user answered on the step before, if this step came from a verdict.
+    #[serde(default)]
+    pub verdict: Option<bool>,
+    /// The dependencies which got enabled for this step, with the mod which needed them.
+    #[serde(default)]
+    enabled_by: HashMap<String, String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir, read_dir, remove_dir, write};
+    use std::path::Path;
+
+    use crate::modpack::{Modpack, SearchMode, Session};
+    use crate::test_util::{instance, load, TempDir};
+    use crate::{Plugin, PluginStatus};
+
+    /// Writes an instance with a Fabric mod for every id, and loads it.
+    fn open(name: &str, ids: &[&str]) -> (TempDir, Modpack) {
+        let game_dir = instance(name, ids);
+        let modpack = load(&game_dir);
+        (game_dir, modpack)
+    }
+
+    /// Puts a directory where the jars are going to be renamed to, which makes the renames fail.
+    fn block(mods: &Path, ids: &[&str], blocked: bool) {
+        for id in ids {
+            for name in [format!("{id}.jar"), format!("{id}.jar.tempdisabled")] {
+                let path = mods.join(name);
+                if blocked && !path.exists() {
+                    create_dir(&path).unwrap();
+                } else if !blocked && path.is_dir() {
+                    remove_dir(&path).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn failed_rename() {
+        let ids = ["a", "b", "c", "d"];
+        let (game_dir, mut modpack) = open("modpack-failed-rename", &ids);
+        let mods = game_dir.join("mods");
+        modpack.set_mode(SearchMode::Ddmin);
+
+        block(&mods, &ids, true);
+        modpack.split();
+        assert_eq!(modpack.step(), 1);
+        assert!(modpack.take_notifications().iter().any(|v| v.title == "Failed to apply changes"));
+        assert!(modpack.plugins().iter().iter().all(|v| v.status == PluginStatus::Enabled));
+        // The search did not start, so the next verdict is the first one again.
+        assert!(modpack.strategy.save().is_null());
+
+        block(&mods, &ids, false);
+        modpack.split();
+        assert_eq!(modpack.step(), 2);
+        let search = modpack.strategy.save();
+        assert!(!search.is_null());
+
+        block(&mods, &ids, true);
+        modpack.undo();
+        assert_eq!(modpack.step(), 2);
+        assert_eq!(modpack.strategy.save(), search);
+
+        block(&mods, &ids, false);
+        modpack.undo();
+        assert_eq!(modpack.step(), 1);
+        assert!(modpack.strategy.save().is_null());
+    }
+
+    #[test]
+    fn corrupt_jar() {
+        let game_dir = instance("modpack-corrupt-jar", &["a", "b"]);
+        write(game_dir.join("mods/half-downloaded.jar"), b"PK\x03\x04").unwrap();
+
+        let mut modpack = load(&game_dir);
+        assert_eq!(modpack.plugins().iter().len(), 2);
+        let notifications = modpack.take_notifications();
+        assert!(notifications.iter().any(|v| v.title == "Failed to load mod"
+            && v.description.contains("half-downloaded.jar")));
+    }
+
+    #[test]
+    fn inspect() {
+        let game_dir = instance("modpack-inspect", &["a", "b"]);
+        let mods = game_dir.join("mods");
+        std::fs::rename(mods.join("b.jar"), mods.join("b.jar.tempdisabled")).unwrap();
+
+        let mut modpack = Modpack::inspect(game_dir.to_path_buf()).unwrap();
+        while modpack.is_loading() {
+            modpack.tick_loader();
+        }
+        // Without a session to continue, a search would have started by enabling every mod.
+        assert!(mods.join("b.jar.tempdisabled").exists());
+        assert_eq!(modpack.plugins().get("b").unwrap().status, PluginStatus::Disabled);
+        assert!(!game_dir.join(".splinter").exists());
+    }
+
+    #[test]
+    fn result() {
+        let (_game_dir, mut modpack) = open("modpack-result", &["a", "b", "c", "d", "e"]);
+        modpack.set_mode(SearchMode::Ddmin);
+
+        // The issue only shows up with both "b" and "d".
+        while modpack.result().is_none() && modpack.step() < 30 {
+            let enabled = |id| modpack.plugins().get(id).is_some_and(|v| v.enabled());
+            let broken = enabled("b") && enabled("d");
+            modpack.verdict(broken);
+        }
+        let mut result = modpack.result().unwrap();
+        result.sort();
+        assert_eq!(result, ["b", "d"]);
+    }
+
+    #[test]
+    fn reload() {
+        let (game_dir, mut modpack) = open("modpack-reload", &["a", "b", "c", "d"]);
+        let files = || {
+            let mut files: Vec<String> = read_dir(game_dir.join("mods"))
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+                .collect();
+            files.sort();
+            files
+        };
+        modpack.split();
+        let split = files();
+        let disabled = split.iter().find(|v| v.ends_with(".tempdisabled")).unwrap();
+
+        // Loading the modpack again continues the search, without touching the files.
+        let plugin = Plugin::new(game_dir.join("mods").join(disabled)).unwrap().unwrap();
+        assert_eq!(plugin.status, PluginStatus::Disabled);
+        assert_eq!(plugin.sha1().len(), 40);
+        assert_eq!(files(), split);
+        let modpack = load(&game_dir);
+        assert_eq!(modpack.step(), 2);
+        assert_eq!(files(), split);
+
+        // Without the session, the search starts over with every mod.
+        Session::remove(&game_dir);
+        load(&game_dir);
+        assert_eq!(files(), ["a.jar", "b.jar", "c.jar", "d.jar"]);
+    }
+}
@@ -0,0 +1,420 @@
+use std::fmt::Write;
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{RecvTimeoutError, Sender, channel};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use tracing::{info, warn};
+
+use crate::modpack::{Modpack, splinter_dir, write_file};
+use crate::{NotificationEvent, Severity};
+
+/// How long the output still gets read after the command exited, a process it started may keep it open.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Decides if the issue showed up by running a command, instead of asking the user.
+///
+/// A matching pattern decides first and stops the command, then the timeout, then the exit code,
+/// where anything but 0 means the issue showed up.
+pub struct Oracle {
+    /// The program and its arguments, run in the game directory.
+    pub command: Vec<String>,
+    pub timeout: Option<Duration>,
+    /// If running into the timeout means the issue showed up, a server which keeps running did start fine.
+    pub timeout_broken: bool,
+    /// A line of output which means the issue showed up.
+    pub broken_pattern: Option<Regex>,
+    /// A line of output which means the issue did not show up.
+    pub fixed_pattern: Option<Regex>,
+}
+
+/// What the command of an [`Oracle`] did.
+pub struct OracleOutput {
+    pub broken: bool,
+    /// Why the oracle decided so, like `exit code 1`.
+    pub reason: String,
+    /// `None` if the command got stopped or was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+    /// Both outputs as they came, the lines of stderr are prefixed with `[stderr]`.
+    pub output: String,
+}
+
+/// A test the oracle answered during [`Modpack::auto_bisect`].
+pub struct OracleRun {
+    pub step: usize,
+    pub broken: bool,
+    pub reason: String,
+    /// Where the command, the enabled mods and the output got written.
+    pub transcript: PathBuf,
+}
+
+enum Line {
+    Output { text: String, stderr: bool },
+    Closed,
+}
+
+impl Oracle {
+    pub fn new(command: Vec<String>) -> Oracle {
+        Oracle {
+            command,
+            timeout: None,
+            timeout_broken: false,
+            broken_pattern: None,
+            fixed_pattern: None,
+        }
+    }
+
+    /// Runs the command until it exits, a pattern matches or it runs into the timeout.
+    pub fn run(&self, dir: &Path) -> io::Result<OracleOutput> {
+        let Some((program, args)) = self.command.split_first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "No command to run",
+            ));
+        };
+
+        let start = Instant::now();
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut process = ProcessGroup::spawn(&mut command)?;
+        let (sender, receiver) = channel();
+        if let Some(stdout) = process.child.stdout.take() {
+            read_lines(stdout, sender.clone(), false);
+        }
+        if let Some(stderr) = process.child.stderr.take() {
+            read_lines(stderr, sender, true);
+        }
+
+        let mut output = String::new();
+        let mut verdict = None;
+        let mut exited_at: Option<Instant> = None;
+        let mut open = 2;
+        while open > 0 || exited_at.is_none() {
+            match receiver.recv_timeout(Duration::from_millis(50)) {
+                Ok(Line::Output { text, stderr }) => {
+                    if verdict.is_none() {
+                        verdict = self.matches(text.trim_end());
+                        if verdict.is_some() && exited_at.is_none() {
+                            process.kill()?;
+                        }
+                    }
+                    if stderr {
+                        output.push_str("[stderr] ");
+                    }
+                    output.push_str(&text);
+                }
+                Ok(Line::Closed) => open -= 1,
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => thread::sleep(Duration::from_millis(50)),
+            }
+
+            if exited_at.is_none() && process.child.try_wait()?.is_some() {
+                exited_at = Some(Instant::now());
+            }
+            if exited_at.is_some_and(|time| time.elapsed() > DRAIN_TIMEOUT) {
+                warn!(
+                    "Stopped reading the output of {program}, a process it started still has it open"
+                );
+                break;
+            }
+            if verdict.is_none()
+                && exited_at.is_none()
+                && self
+                    .timeout
+                    .is_some_and(|timeout| start.elapsed() > timeout)
+            {
+                process.kill()?;
+                verdict = Some((self.timeout_broken, "timed out".to_string()));
+            }
+        }
+
+        // Nothing the command started may keep running, the next step renames the jars it has open.
+        process.kill()?;
+        let exit_code = process.child.wait()?.code();
+        let (broken, reason) = verdict.unwrap_or_else(|| match exit_code {
+            Some(code) => (code != 0, format!("exit code {code}")),
+            None => (true, "killed by a signal".to_string()),
+        });
+        Ok(OracleOutput {
+            broken,
+            reason,
+            exit_code,
+            duration: start.elapsed(),
+            output,
+        })
+    }
+
+    fn matches(&self, line: &str) -> Option<(bool, String)> {
+        for (pattern, broken) in [(&self.broken_pattern, true), (&self.fixed_pattern, false)] {
+            if let Some(pattern) = pattern.as_ref().filter(|pattern| pattern.is_match(line)) {
+                return Some((broken, format!("matched \"{pattern}\"")));
+            }
+        }
+        None
+    }
+}
+
+/// The command along with every process it starts, like the game started by a launch script.
+struct ProcessGroup {
+    child: Child,
+    #[cfg(windows)]
+    job: windows_sys::Win32::Foundation::HANDLE,
+}
+
+impl ProcessGroup {
+    #[cfg(unix)]
+    fn spawn(command: &mut Command) -> io::Result<ProcessGroup> {
+        use std::os::unix::process::CommandExt;
+
+        let child = command.process_group(0).spawn()?;
+        Ok(ProcessGroup { child })
+    }
+
+    #[cfg(windows)]
+    fn spawn(command: &mut Command) -> io::Result<ProcessGroup> {
+        use std::os::windows::io::AsRawHandle;
+        use windows_sys::Win32::System::JobObjects::{AssignProcessToJobObject, CreateJobObjectW};
+
+        let child = command.spawn()?;
+        // The processes started by a process in a job are in the job as well.
+        let job = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+        if job.is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        let process = ProcessGroup { child, job };
+        if unsafe { AssignProcessToJobObject(job, process.child.as_raw_handle()) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(process)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn spawn(command: &mut Command) -> io::Result<ProcessGroup> {
+        Ok(ProcessGroup { child: command.spawn()? })
+    }
+
+    /// Kills the command and every process it started which is still running.
+    #[cfg(unix)]
+    fn kill(&mut self) -> io::Result<()> {
+        // The group has the id of the command, as it was started as the leader of a new group.
+        if unsafe { libc::killpg(self.child.id() as libc::pid_t, libc::SIGKILL) } != 0 {
+            let error = io::Error::last_os_error();
+            // Everything in the group already exited.
+            if error.raw_os_error() != Some(libc::ESRCH) {
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn kill(&mut self) -> io::Result<()> {
+        use windows_sys::Win32::System::JobObjects::TerminateJobObject;
+
+        if unsafe { TerminateJobObject(self.job, 1) } == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn kill(&mut self) -> io::Result<()> {
+        self.child.kill()
+    }
+}
+
+#[cfg(windows)]
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        unsafe { windows_sys::Win32::Foundation::CloseHandle(self.job) };
+    }
+}
+
+fn read_lines(reader: impl Read + Send + 'static, sender: Sender<Line>, stderr: bool) {
+    thread::spawn(move || {
+        let mut reader = BufReader::new(reader);
+        let mut buffer = Vec::new();
+        while let Ok(1..) = reader.read_until(b'\n', &mut buffer) {
+            let text = String::from_utf8_lossy(&buffer).to_string();
+            if sender.send(Line::Output { text, stderr }).is_err() {
+                return;
+            }
+            buffer.clear();
+        }
+        let _ = sender.send(Line::Closed);
+    });
+}
+
+impl Modpack {
+    /// Tests the enabled mods with the oracle and continues the search with its verdict, until the search is done.
+    ///
+    /// Stops early when the issue does not show up with every mod, a verdict could not be applied,
+    /// or after `max_steps` tests. The transcripts of a call are kept together in `.splinter/oracle/run-<time>`,
+    /// so the ones of earlier calls stay around.
+    pub fn auto_bisect(&mut self, oracle: &Oracle, max_steps: usize) -> io::Result<Vec<OracleRun>> {
+        let mut started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let run_dir = |started| splinter_dir(&self.path).join("oracle").join(format!("run-{started}"));
+        while run_dir(started).exists() {
+            started += 1;
+        }
+        let transcripts = run_dir(started);
+        let mut runs = Vec::new();
+        // The estimate is only a guess, the search is done once it has a result.
+        while !self.is_loading() && self.result().is_none() {
+            if runs.len() >= max_steps {
+                self.notifications.push(NotificationEvent {
+                    title: "Stopped searching".to_string(),
+                    description: format!("The search is not done after {max_steps} tests"),
+                    ty: Severity::Warning,
+                });
+                break;
+            }
+
+            let step = self.step();
+            let enabled = self.enabled_ids(|plugin| plugin.status);
+            info!("Testing step {step} with {} mods enabled", enabled.len());
+            let output = oracle.run(&self.path)?;
+            info!(
+                "Step {step} is {}, {}",
+                if output.broken { "broken" } else { "fixed" },
+                output.reason
+            );
+
+            let transcript = transcripts.join(format!("step-{step:03}.log"));
+            write_file(
+                &transcript,
+                transcript_text(oracle, &enabled, &output).as_bytes(),
+            )?;
+            runs.push(OracleRun {
+                step,
+                broken: output.broken,
+                reason: output.reason,
+                transcript,
+            });
+
+            if step == 1 && !output.broken {
+                self.notifications.push(NotificationEvent {
+                    title: "Nothing to search".to_string(),
+                    description: "The issue did not show up with every mod enabled".to_string(),
+                    ty: Severity::Error,
+                });
+                break;
+            }
+            self.verdict(output.broken);
+            if self.step() == step {
+                // The verdict did not apply, the notifications tell why.
+                break;
+            }
+        }
+
+        Ok(runs)
+    }
+}
+
+fn transcript_text(oracle: &Oracle, enabled: &[String], output: &OracleOutput) -> String {
+    let mut text = String::new();
+    let _ = writeln!(text, "Command: {}", oracle.command.join(" "));
+    let _ = writeln!(text, "Enabled: {}", enabled.join(", "));
+    let _ = writeln!(
+        text,
+        "Verdict: {}, {} after {:.1}s",
+        if output.broken { "broken" } else { "fixed" },
+        output.reason,
+        output.duration.as_secs_f64()
+    );
+    text.push('\n');
+    text.push_str(&output.output);
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use regex::Regex;
+
+    use crate::modpack::oracle::Oracle;
+    use crate::test_util::{instance, load};
+
+    fn shell(script: &str) -> Oracle {
+        Oracle::new(vec!["sh".to_string(), "-c".to_string(), script.to_string()])
+    }
+
+    #[test]
+    fn verdicts() {
+        let dir = std::env::temp_dir();
+        let output = shell("echo starting; exit 1").run(&dir).unwrap();
+        assert!(output.broken);
+        assert_eq!(output.exit_code, Some(1));
+        assert_eq!(output.output, "starting\n");
+        assert!(!shell("exit 0").run(&dir).unwrap().broken);
+
+        // The pattern decides before the exit code, and stops the command.
+        let mut oracle = shell("echo Done >&2; sleep 10; exit 1");
+        oracle.fixed_pattern = Some(Regex::new("^Done$").unwrap());
+        let output = oracle.run(&dir).unwrap();
+        assert!(!output.broken);
+        assert!(output.duration < Duration::from_secs(5));
+
+        let mut oracle = shell("sleep 10");
+        oracle.timeout = Some(Duration::from_millis(200));
+        oracle.timeout_broken = true;
+        let output = oracle.run(&dir).unwrap();
+        assert!(output.broken);
+        assert_eq!(output.reason, "timed out");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn kills_children() {
+        // The shell waits on a child of its own, which keeps the output open when only the shell is killed.
+        let mut oracle = shell("sleep 100 & echo $!; wait");
+        oracle.timeout = Some(Duration::from_millis(200));
+        let output = oracle.run(&std::env::temp_dir()).unwrap();
+        assert_eq!(output.reason, "timed out");
+        assert!(output.duration < Duration::from_secs(1));
+
+        let pid: u32 = output.output.trim().parse().unwrap();
+        // Killed children stay as zombies until they are reaped, they are not running either way.
+        // The signal is delivered asynchronously, so the child gets a moment to act on it.
+        let mut stat = String::new();
+        for _ in 0..50 {
+            stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).unwrap_or_default();
+            if stat.is_empty() || stat.contains(") Z ") {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(stat.is_empty() || stat.contains(") Z "), "{stat}");
+    }
+    #[test]
+    fn auto_bisect() {
+        let game_dir = instance("oracle-auto-bisect", &["a", "b", "c", "d", "e"]);
+        let mut modpack = load(&game_dir);
+        // The issue shows up while "c" is enabled.
+        let runs = modpack.auto_bisect(&shell("test ! -f mods/c.jar"), 20).unwrap();
+        assert_eq!(modpack.result(), Some(vec!["c".to_string()]));
+        assert_eq!(runs.len(), modpack.step() - 1);
+
+        // Another run keeps the transcripts of the first one.
+        let mut modpack = load(&game_dir);
+        modpack.undo();
+        let again = modpack.auto_bisect(&shell("test ! -f mods/c.jar"), 20).unwrap();
+        assert_eq!(again.len(), 1);
+        assert_ne!(again[0].transcript.parent(), runs[0].transcript.parent());
+        assert!(runs.iter().chain(&again).all(|run| run.transcript.is_file()));
+    }
+}
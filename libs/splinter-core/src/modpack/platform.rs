@@ -0,0 +1,553 @@
+use std::collections::HashMap;
+use std::fs::read_to_string;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use crate::Dependency;
+
+/// What the game, Java and every mod loader provide instead of a jar in the mods folder.
+/// Dependencies on any of these ids are checked against the platform.
+const LOADERS: [LoaderIds; 6] = [
+    LoaderIds {
+        loader: Loader::Minecraft,
+        name: "Minecraft",
+        ids: &["minecraft", "mcp"],
+        builtin: None,
+    },
+    LoaderIds {
+        loader: Loader::Java,
+        name: "Java",
+        ids: &["java"],
+        builtin: None,
+    },
+    LoaderIds {
+        loader: Loader::Fabric,
+        name: "Fabric",
+        ids: &["fabricloader", "fabric-loader"],
+        builtin: Some(("MixinExtras", &["mixinextras"])),
+    },
+    LoaderIds {
+        loader: Loader::Quilt,
+        name: "Quilt",
+        ids: &["quilt_loader"],
+        builtin: Some(("Fabric compatibility", &["fabricloader", "mixinextras"])),
+    },
+    LoaderIds {
+        loader: Loader::Forge,
+        name: "Forge",
+        ids: &["forge", "Forge", "FML", "fml", "javafml"],
+        builtin: None,
+    },
+    LoaderIds {
+        loader: Loader::NeoForge,
+        name: "NeoForge",
+        ids: &["neoforge", "fml", "javafml"],
+        builtin: None,
+    },
+];
+
+struct LoaderIds {
+    loader: Loader,
+    name: &'static str,
+    ids: &'static [&'static str],
+    /// Mods which come with the loader, but have their own version.
+    builtin: Option<(&'static str, &'static [&'static str])>,
+}
+
+/// The game and mod loader of an instance, which mods depend on like any other mod.
+pub struct Platform {
+    pub components: Vec<PlatformComponent>,
+    /// If we found out what is installed, otherwise every platform dependency is unknown.
+    pub detected: bool,
+}
+
+pub struct PlatformComponent {
+    pub name: &'static str,
+    pub ids: &'static [&'static str],
+    pub version: Option<String>,
+}
+
+pub enum PlatformStatus {
+    Satisfied,
+    /// We could not find out the version, so we can not tell.
+    Unknown,
+    Missing,
+    Mismatch(String),
+}
+
+impl Platform {
+    /// Reads the platform out of the launcher files around the game directory.
+    pub fn new(game_dir: &Path) -> Platform {
+        let platform = Self::new_multimc(game_dir)
+            .or_else(|| Self::new_modrinth(game_dir))
+            .or_else(|| Self::new_curseforge(game_dir))
+            .or_else(|| Self::new_atlauncher(game_dir))
+            .or_else(|| Self::new_vanilla(game_dir));
+        match platform {
+            Some(platform) => {
+                info!(
+                    "Detected platform {}",
+                    platform
+                        .components
+                        .iter()
+                        .map(|component| format!(
+                            "{} {}",
+                            component.name,
+                            component.version.as_deref().unwrap_or("?")
+                        ))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+                platform
+            }
+            None => {
+                debug!("Could not detect the platform of {game_dir:?}");
+                Platform {
+                    components: vec![],
+                    detected: false,
+                }
+            }
+        }
+    }
+
+    /// MultiMC and Prism Launcher keep their components in a `mmc-pack.json` next to the game directory.
+    fn new_multimc(game_dir: &Path) -> Option<Platform> {
+        let instance = [Some(game_dir), game_dir.parent()]
+            .into_iter()
+            .flatten()
+            .find(|dir| dir.join("mmc-pack.json").exists())?;
+        let pack: MultiMcPack =
+            serde_json::from_str(&read_to_string(instance.join("mmc-pack.json")).ok()?).ok()?;
+
+        let mut platform = Platform {
+            components: vec![],
+            detected: true,
+        };
+        for component in pack.components {
+            let loader = match component.uid.as_str() {
+                "net.minecraft" => Loader::Minecraft,
+                "net.fabricmc.fabric-loader" => Loader::Fabric,
+                "org.quiltmc.quilt-loader" => Loader::Quilt,
+                "net.minecraftforge" => Loader::Forge,
+                "net.neoforged" => Loader::NeoForge,
+                _ => continue,
+            };
+            platform.add(loader, component.version);
+        }
+
+        // Prism stores the java version it last launched the instance with.
+        let java = read_to_string(instance.join("instance.cfg"))
+            .ok()
+            .and_then(|config| {
+                config.lines().find_map(|line| {
+                    let (key, value) = line.split_once('=')?;
+                    (key.trim() == "JavaVersion").then(|| value.trim().to_string())
+                })
+            });
+        platform.add(Loader::Java, java);
+        Some(platform)
+    }
+
+    /// The Modrinth App keeps the versions in the `profile.json` of the profile.
+    fn new_modrinth(game_dir: &Path) -> Option<Platform> {
+        let profile: ModrinthProfile =
+            serde_json::from_str(&read_to_string(game_dir.join("profile.json")).ok()?).ok()?;
+        let metadata = profile.metadata;
+
+        let mut platform = Platform {
+            components: vec![],
+            detected: true,
+        };
+        platform.add(Loader::Minecraft, Some(metadata.game_version));
+        if let Some(loader) = Loader::parse(&metadata.loader) {
+            platform.add(loader, metadata.loader_version.map(|version| version.id));
+        }
+        platform.add(Loader::Java, None);
+        Some(platform)
+    }
+
+    /// The CurseForge app names the loader after its version, like `fabric-0.15.11-1.20.1` or `forge-47.2.0`.
+    fn new_curseforge(game_dir: &Path) -> Option<Platform> {
+        let instance: CurseForgeInstance = serde_json::from_str(
+            &read_to_string(game_dir.join("minecraftinstance.json")).ok()?,
+        )
+        .ok()?;
+
+        let mut platform = Platform {
+            components: vec![],
+            detected: true,
+        };
+        platform.add(Loader::Minecraft, instance.game_version);
+        if let Some(name) = instance.base_mod_loader.and_then(|loader| loader.name) {
+            let (loader, version) = name.split_once('-').unwrap_or((&name, ""));
+            let version = version.split('-').next().unwrap_or_default();
+            if let Some(loader) = Loader::parse(loader) {
+                platform.add(loader, Some(version.to_string()));
+            }
+        }
+        platform.add(Loader::Java, None);
+        Some(platform)
+    }
+
+    /// ATLauncher keeps the versions in the `instance.json` of the instance.
+    fn new_atlauncher(game_dir: &Path) -> Option<Platform> {
+        let instance: AtLauncherInstance =
+            serde_json::from_str(&read_to_string(game_dir.join("instance.json")).ok()?).ok()?;
+
+        let mut platform = Platform {
+            components: vec![],
+            detected: true,
+        };
+        platform.add(Loader::Minecraft, instance.id);
+        if let Some(version) = instance.launcher.loader_version {
+            if let Some(loader) = Loader::parse(&version.r#type) {
+                platform.add(loader, version.version);
+            }
+        }
+        platform.add(Loader::Java, None);
+        Some(platform)
+    }
+
+    /// The vanilla launcher names the versions after the loader, like `fabric-loader-0.15.11-1.20.1`.
+    fn new_vanilla(game_dir: &Path) -> Option<Platform> {
+        let profiles: LauncherProfiles =
+            serde_json::from_str(&read_to_string(game_dir.join("launcher_profiles.json")).ok()?)
+                .ok()?;
+        let profile = profiles
+            .profiles
+            .into_values()
+            .filter(|profile| profile.last_version_id.is_some())
+            .max_by(|v0, v1| v0.last_used.cmp(&v1.last_used))?;
+        let id = profile.last_version_id?;
+
+        let version: Option<LauncherVersion> = read_to_string(
+            game_dir
+                .join("versions")
+                .join(&id)
+                .join(format!("{id}.json")),
+        )
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok());
+        let inherits_from = version.and_then(|version| version.inherits_from);
+
+        let mut platform = Platform {
+            components: vec![],
+            detected: true,
+        };
+        if let Some(rest) = id.strip_prefix("fabric-loader-") {
+            let (loader, minecraft) = rest.split_once('-').unwrap_or((rest, ""));
+            platform.add(Loader::Fabric, Some(loader.to_string()));
+            platform.add(
+                Loader::Minecraft,
+                inherits_from.or(Some(minecraft.to_string())),
+            );
+        } else if let Some(rest) = id.strip_prefix("quilt-loader-") {
+            let (loader, minecraft) = rest.split_once('-').unwrap_or((rest, ""));
+            platform.add(Loader::Quilt, Some(loader.to_string()));
+            platform.add(
+                Loader::Minecraft,
+                inherits_from.or(Some(minecraft.to_string())),
+            );
+        } else if let Some(loader) = id.strip_prefix("neoforge-") {
+            platform.add(Loader::NeoForge, Some(loader.to_string()));
+            platform.add(Loader::Minecraft, inherits_from);
+        } else if let Some((minecraft, loader)) = id.split_once("-forge-") {
+            platform.add(Loader::Forge, Some(loader.to_string()));
+            platform.add(
+                Loader::Minecraft,
+                inherits_from.or(Some(minecraft.to_string())),
+            );
+        } else {
+            platform.add(Loader::Minecraft, Some(id));
+        }
+        platform.add(Loader::Java, None);
+        Some(platform)
+    }
+
+    fn add(&mut self, loader: Loader, version: Option<String>) {
+        let version = version.filter(|version| !version.is_empty());
+        let entry = loader.entry();
+        self.components.push(PlatformComponent {
+            name: entry.name,
+            ids: entry.ids,
+            // Java reports 8 as 1.8, while mods depend on 8.
+            version: match loader {
+                Loader::Java => version.map(|v| v.strip_prefix("1.").unwrap_or(&v).to_string()),
+                _ => version,
+            },
+        });
+
+        if let Some((name, ids)) = entry.builtin {
+            self.components.push(PlatformComponent {
+                name,
+                ids,
+                version: None,
+            });
+        }
+    }
+
+    /// The mod loader with its version, like `Fabric 0.15.11`.
+    pub fn loader(&self) -> Option<String> {
+        self.components
+            .iter()
+            .find(|component| {
+                LOADERS
+                    .iter()
+                    .any(|entry| entry.loader.is_mod_loader() && entry.name == component.name)
+            })
+            .map(|component| match &component.version {
+                Some(version) => format!("{} {version}", component.name),
+                None => component.name.to_string(),
+            })
+    }
+
+    pub fn minecraft(&self) -> Option<&str> {
+        self.components
+            .iter()
+            .find(|component| component.name == "Minecraft")?
+            .version
+            .as_deref()
+    }
+
+    /// Checks a dependency against the platform, returns `None` if the dependency is not on the platform.
+    pub fn check(&self, dependency: &Dependency) -> Option<PlatformStatus> {
+        let id = dependency.id.as_str();
+        let on_platform = LOADERS.iter().any(|entry| {
+            entry.ids.contains(&id) || entry.builtin.is_some_and(|(_, ids)| ids.contains(&id))
+        });
+        if !on_platform {
+            return None;
+        }
+
+        let component = self
+            .components
+            .iter()
+            .find(|component| component.ids.contains(&id));
+
+        Some(match component {
+            Some(PlatformComponent {
+                version: Some(version),
+                ..
+            }) => {
+                if dependency.version.matches(version) {
+                    PlatformStatus::Satisfied
+                } else {
+                    PlatformStatus::Mismatch(version.clone())
+                }
+            }
+            Some(_) => PlatformStatus::Unknown,
+            None if self.detected => PlatformStatus::Missing,
+            None => PlatformStatus::Unknown,
+        })
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Loader {
+    Minecraft,
+    Java,
+    Fabric,
+    Quilt,
+    Forge,
+    NeoForge,
+}
+
+impl Loader {
+    /// Reads the name launchers give a mod loader.
+    fn parse(name: &str) -> Option<Loader> {
+        LOADERS
+            .iter()
+            .find(|entry| entry.loader.is_mod_loader() && entry.name.eq_ignore_ascii_case(name))
+            .map(|entry| entry.loader)
+    }
+
+    fn is_mod_loader(self) -> bool {
+        !matches!(self, Loader::Minecraft | Loader::Java)
+    }
+
+    fn entry(self) -> &'static LoaderIds {
+        LOADERS
+            .iter()
+            .find(|entry| entry.loader == self)
+            .expect("every loader is in LOADERS")
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MultiMcPack {
+    #[serde(default)]
+    components: Vec<MultiMcComponent>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct MultiMcComponent {
+    uid: String,
+    version: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LauncherProfiles {
+    #[serde(default)]
+    profiles: HashMap<String, LauncherProfile>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherProfile {
+    last_version_id: Option<String>,
+    // An ISO-8601 timestamp, which sorts correctly as text.
+    last_used: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LauncherVersion {
+    inherits_from: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModrinthProfile {
+    metadata: ModrinthMetadata,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModrinthMetadata {
+    game_version: String,
+    loader: String,
+    loader_version: Option<ModrinthLoaderVersion>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ModrinthLoaderVersion {
+    id: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeInstance {
+    game_version: Option<String>,
+    base_mod_loader: Option<CurseForgeLoader>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CurseForgeLoader {
+    name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AtLauncherInstance {
+    /// The version of the game.
+    id: Option<String>,
+    launcher: AtLauncherLauncher,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AtLauncherLauncher {
+    loader_version: Option<AtLauncherLoaderVersion>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AtLauncherLoaderVersion {
+    r#type: String,
+    version: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{create_dir_all, write};
+
+    use crate::modpack::platform::{Loader, Platform, PlatformStatus, LOADERS};
+    use crate::test_util::TempDir;
+    use crate::{Dependency, VersionRange};
+
+    #[test]
+    fn multimc() {
+        let instance = TempDir::new("platform-multimc");
+        let game_dir = instance.join(".minecraft");
+        create_dir_all(&game_dir).unwrap();
+        write(
+            instance.join("mmc-pack.json"),
+            r#"{
+                "components": [
+                    { "uid": "net.minecraft", "version": "1.20.1" },
+                    { "uid": "net.fabricmc.intermediary", "version": "1.20.1" },
+                    { "uid": "net.fabricmc.fabric-loader", "version": "0.15.11" }
+                ],
+                "formatVersion": 1
+            }"#,
+        )
+        .unwrap();
+        write(
+            instance.join("instance.cfg"),
+            "JavaVersion=17.0.8\nname=Test\n",
+        )
+        .unwrap();
+
+        let platform = Platform::new(&game_dir);
+
+        let check = |id: &str, range: &str| {
+            platform.check(&Dependency::new(id, VersionRange::parse(range)))
+        };
+        assert!(matches!(
+            check("minecraft", "~1.20"),
+            Some(PlatformStatus::Satisfied)
+        ));
+        assert!(
+            matches!(check("minecraft", "1.19.x"), Some(PlatformStatus::Mismatch(v)) if v == "1.20.1")
+        );
+        assert!(matches!(
+            check("fabricloader", ">=0.15"),
+            Some(PlatformStatus::Satisfied)
+        ));
+        assert!(matches!(
+            check("java", ">=17"),
+            Some(PlatformStatus::Satisfied)
+        ));
+        assert!(matches!(
+            check("mixinextras", "*"),
+            Some(PlatformStatus::Unknown)
+        ));
+        assert!(matches!(check("forge", "*"), Some(PlatformStatus::Missing)));
+        assert!(check("sodium", "*").is_none());
+    }
+
+    #[test]
+    fn curseforge() {
+        let game_dir = TempDir::new("platform-curseforge");
+        write(
+            game_dir.join("minecraftinstance.json"),
+            r#"{
+                "name": "Pack",
+                "gameVersion": "1.20.1",
+                "baseModLoader": { "name": "fabric-0.15.11-1.20.1", "minecraftVersion": "1.20.1" }
+            }"#,
+        )
+        .unwrap();
+
+        let platform = Platform::new(&game_dir);
+        assert_eq!(platform.loader().as_deref(), Some("Fabric 0.15.11"));
+        assert_eq!(platform.minecraft(), Some("1.20.1"));
+    }
+    #[test]
+    fn loaders() {
+        // Every id of a loader is checked against the platform, even when the loader is not installed.
+        let platform = Platform {
+            components: vec![],
+            detected: true,
+        };
+        for entry in &LOADERS {
+            let builtin = entry.builtin.map_or(&[][..], |(_, ids)| ids);
+            for id in entry.ids.iter().chain(builtin) {
+                let dependency = Dependency::new(*id, VersionRange::any());
+                assert!(matches!(platform.check(&dependency), Some(PlatformStatus::Missing)), "{id}");
+            }
+        }
+
+        assert!(matches!(Loader::parse("Fabric"), Some(Loader::Fabric)));
+        assert!(matches!(Loader::parse("neoforge"), Some(Loader::NeoForge)));
+        assert!(Loader::parse("minecraft").is_none());
+    }
+}
@@ -0,0 +1,305 @@
+use std::collections::HashSet;
+
+use rand::prelude::SliceRandom;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::modpack::PluginList;
+use crate::modpack::search::{
+    Estimate, Proposal, SearchConfig, SearchContext, SearchStrategy,
+};
+use crate::{NotificationEvent, Severity};
+
+/// How often the issue shows up without the faulty mod, which is assumed to be rare.
+const FALSE_POSITIVE: f64 = 0.01;
+/// How many random orders of the mods are tried when picking the next test.
+const ATTEMPTS: usize = 16;
+
+/// A probabilistic bisection, which keeps the chance of every mod being the faulty one.
+///
+/// No verdict rules a mod out for good, a wrong "fixed" only makes the faulty mod less likely,
+/// and the tests afterwards make up for it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bayes {
+    suspicion: Vec<(String, f64)>,
+    /// If the user was told about the most likely mod.
+    reported: bool,
+}
+
+impl Bayes {
+    pub fn new(candidates: Vec<String>) -> Bayes {
+        let prior = 1.0 / candidates.len().max(1) as f64;
+        Bayes {
+            suspicion: candidates.into_iter().map(|id| (id, prior)).collect(),
+            reported: false,
+        }
+    }
+
+    /// Updates the suspicion of every mod with the result of testing with the mods enabled.
+    pub fn verdict(&mut self, tested: &[String], broken: bool, config: &SearchConfig) {
+        let tested: HashSet<&String> = tested.iter().collect();
+        for (id, suspicion) in &mut self.suspicion {
+            let likelihood = match (tested.contains(id), broken) {
+                (true, true) => 1.0 - config.false_negative,
+                (true, false) => config.false_negative,
+                (false, true) => FALSE_POSITIVE,
+                (false, false) => 1.0 - FALSE_POSITIVE,
+            };
+            *suspicion *= likelihood;
+        }
+
+        let total: f64 = self.suspicion.iter().map(|(_, v)| v).sum();
+        if total > 0.0 {
+            for (_, suspicion) in &mut self.suspicion {
+                *suspicion /= total;
+            }
+        }
+    }
+
+    /// How much a test with this much suspicion enabled tells us, in bits.
+    fn information_gain(mass: f64, config: &SearchConfig) -> f64 {
+        let broken = mass * (1.0 - config.false_negative) + (1.0 - mass) * FALSE_POSITIVE;
+        entropy(broken)
+            - mass * entropy(config.false_negative)
+            - (1.0 - mass) * entropy(FALSE_POSITIVE)
+    }
+
+    /// Picks the mods to enable next, the closure adds the mods which would be enabled along with a set.
+    pub fn propose(
+        &self,
+        config: &SearchConfig,
+        closure: impl Fn(&[String]) -> Vec<String>,
+    ) -> Vec<String> {
+        let mass = |set: &[String]| -> f64 {
+            self.suspicion
+                .iter()
+                .filter(|(id, _)| set.contains(id))
+                .map(|(_, v)| v)
+                .sum()
+        };
+
+        // The suspicion to enable for the most informative test.
+        let target = (0..=100)
+            .map(|i| i as f64 / 100.0)
+            .max_by(|v0, v1| {
+                Self::information_gain(*v0, config).total_cmp(&Self::information_gain(*v1, config))
+            })
+            .unwrap_or(0.5);
+
+        let mut orders = vec![];
+        let mut sorted: Vec<&String> = self.suspicion.iter().map(|(id, _)| id).collect();
+        orders.push(sorted.clone());
+        let mut rng = rand::thread_rng();
+        for _ in 0..ATTEMPTS {
+            sorted.shuffle(&mut rng);
+            orders.push(sorted.clone());
+        }
+
+        let mut best: Option<(f64, Vec<String>)> = None;
+        for order in orders {
+            let mut set: Vec<String> = vec![];
+            let mut set_mass = 0.0;
+            for id in order {
+                if set.contains(id) {
+                    continue;
+                }
+
+                let mut next = set.clone();
+                next.push(id.clone());
+                let next = closure(&next);
+                let next_mass = mass(&next);
+                if (next_mass - target).abs() < (set_mass - target).abs() {
+                    set = next;
+                    set_mass = next_mass;
+                }
+            }
+
+            let gain = Self::information_gain(set_mass, config);
+            if best.as_ref().is_none_or(|(v, _)| gain > *v) {
+                best = Some((gain, set));
+            }
+        }
+
+        best.map(|(_, set)| set).unwrap_or_default()
+    }
+
+    pub fn suspicion(&self, id: &str) -> f64 {
+        self.suspicion
+            .iter()
+            .find(|(v, _)| v == id)
+            .map_or(0.0, |(_, v)| *v)
+    }
+
+    pub fn most_likely(&self) -> Option<(&str, f64)> {
+        self.suspicion
+            .iter()
+            .max_by(|(_, v0), (_, v1)| v0.total_cmp(v1))
+            .map(|(id, v)| (id.as_str(), *v))
+    }
+
+    /// About how many tests it takes until the most likely mod is certain enough,
+    /// if every test tells as much as the most informative one.
+    pub fn expected_tests(&self, config: &SearchConfig) -> f64 {
+        let len = self.suspicion.len();
+        if len <= 1 || self.result(config).is_some() {
+            return 0.0;
+        }
+
+        let uncertainty: f64 = self
+            .suspicion
+            .iter()
+            .filter(|(_, v)| *v > 0.0)
+            .map(|(_, v)| -v * v.log2())
+            .sum();
+        // The uncertainty left once a mod reaches the confidence, with the rest spread evenly.
+        let target =
+            entropy(config.confidence) + (1.0 - config.confidence) * ((len - 1) as f64).log2();
+        let gain = (1..100)
+            .map(|i| Self::information_gain(i as f64 / 100.0, config))
+            .fold(0.0, f64::max);
+        if gain <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        ((uncertainty - target) / gain).ceil().max(1.0)
+    }
+
+    /// The faulty mod, once we are sure enough about it.
+    pub fn result(&self, config: &SearchConfig) -> Option<&str> {
+        self.most_likely()
+            .filter(|(_, v)| *v >= config.confidence)
+            .map(|(id, _)| id)
+    }
+}
+
+fn entropy(p: f64) -> f64 {
+    if p <= 0.0 || p >= 1.0 {
+        return 0.0;
+    }
+    -p * p.log2() - (1.0 - p) * (1.0 - p).log2()
+}
+
+/// Searches with [`Bayes`], starting with the mods which were enabled when the issue first showed up.
+#[derive(Default)]
+pub struct BayesSearch {
+    bayes: Option<Bayes>,
+}
+
+impl SearchStrategy for BayesSearch {
+    fn propose(&mut self, search: &SearchContext) -> Option<Proposal> {
+        let bayes = search.advance(&mut self.bayes, Bayes::new, |bayes, last| {
+            bayes.verdict(&last.enabled, last.broken, search.config)
+        })?;
+
+        let mut notification = None;
+        if let Some(id) = bayes.result(search.config) {
+            if !bayes.reported {
+                let name = search.plugins.get(id).map_or(id, |v| &v.metadata.name);
+                notification = Some(NotificationEvent {
+                    title: "Found the faulty mod".to_string(),
+                    description: format!(
+                        "\"{name}\" causes the issue with a chance of {:.1}%, more tests make it more certain",
+                        bayes.suspicion(id) * 100.0
+                    ),
+                    ty: Severity::Info,
+                });
+                bayes.reported = true;
+            }
+        }
+
+        // Mods which are way less likely than at the start are shown as not faulty.
+        let unlikely = 0.01 / bayes.suspicion.len().max(1) as f64;
+        Some(Proposal {
+            enabled: bayes.propose(search.config, |set| search.plugins.dependency_closure(set)),
+            suspects: bayes
+                .suspicion
+                .iter()
+                .filter(|(_, v)| *v >= unlikely)
+                .map(|(id, _)| id.clone())
+                .collect(),
+            notification,
+        })
+    }
+
+    fn estimate(&self, search: &SearchContext) -> Estimate {
+        let expected = match &self.bayes {
+            Some(bayes) => bayes.expected_tests(search.config),
+            // The first test is on the whole modpack.
+            None => 1.0 + Bayes::new(search.splittable()).expected_tests(search.config),
+        };
+        // Any verdict could be wrong, so there is no limit to how long it takes.
+        Estimate {
+            expected,
+            worst: None,
+        }
+    }
+
+    fn result(&self, search: &SearchContext) -> Option<Vec<String>> {
+        let id = self.bayes.as_ref()?.result(search.config)?;
+        Some(vec![id.to_string()])
+    }
+
+    fn progress(&self, plugins: &PluginList) -> Option<String> {
+        let (id, suspicion) = self.bayes.as_ref()?.most_likely()?;
+        let name = plugins.get(id).map_or(id, |v| &v.metadata.name);
+        Some(format!("Most likely {name} ({:.0}%)", suspicion * 100.0))
+    }
+
+    fn save(&self) -> Value {
+        serde_json::to_value(&self.bayes).unwrap_or_default()
+    }
+
+    fn load(&mut self, state: &Value) {
+        self.bayes = serde_json::from_value(state.clone()).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::modpack::search::SearchConfig;
+    use crate::modpack::search::bayes::Bayes;
+
+    #[test]
+    fn flaky() {
+        let config = SearchConfig {
+            false_negative: 0.3,
+            confidence: 0.95,
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut bayes = Bayes::new((0..50).map(|i| i.to_string()).collect());
+        let expected = bayes.expected_tests(&config);
+        assert!((6.0..40.0).contains(&expected), "expected {expected} tests");
+
+        let mut tests = 0;
+        while bayes.result(&config).is_none() && tests < 100 {
+            let tested = bayes.propose(&config, |set| set.to_vec());
+            // The issue only shows up 70% of the time.
+            let broken = tested.iter().any(|id| id == "23") && rng.gen_bool(0.7);
+            bayes.verdict(&tested, broken, &config);
+            tests += 1;
+        }
+
+        assert_eq!(bayes.result(&config), Some("23"));
+        assert!(tests < 40, "took {tests} tests");
+        assert_eq!(bayes.expected_tests(&config), 0.0);
+    }
+
+    #[test]
+    fn recovers_from_mistake() {
+        let config = SearchConfig::default();
+        let mut bayes = Bayes::new(vec!["a".to_string(), "b".to_string()]);
+
+        // The faulty mod "a" did not show the issue once.
+        let tested = vec!["a".to_string()];
+        bayes.verdict(&tested, false, &config);
+        assert!(bayes.suspicion("a") < bayes.suspicion("b"));
+
+        for _ in 0..3 {
+            bayes.verdict(&tested, true, &config);
+        }
+        assert_eq!(bayes.result(&config), Some("a"));
+    }
+}
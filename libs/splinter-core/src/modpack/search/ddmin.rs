@@ -0,0 +1,249 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::modpack::PluginList;
+use crate::modpack::search::{Estimate, Proposal, SearchContext, SearchStrategy};
+use crate::{NotificationEvent, Severity};
+
+/// The state of a ddmin search, see "Simplifying and Isolating Failure-Inducing Input" by Zeller and Hildebrandt.
+///
+/// The candidates are split into chunks, and every chunk and its complement get tested on their own.
+/// If one of them still has the issue, the search continues with it,
+/// otherwise the chunks get smaller until every single mod has been left out once.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Ddmin {
+    /// The smallest set of mods known to have the issue.
+    candidates: Vec<String>,
+    granularity: usize,
+    complement: bool,
+    index: usize,
+    done: bool,
+}
+
+impl Ddmin {
+    pub fn new(candidates: Vec<String>) -> Ddmin {
+        Ddmin {
+            done: candidates.len() <= 1,
+            candidates,
+            granularity: 2,
+            complement: false,
+            index: 0,
+        }
+    }
+
+    fn chunk(&self, index: usize) -> &[String] {
+        let len = self.candidates.len();
+        let start = index * len / self.granularity;
+        let end = (index + 1) * len / self.granularity;
+        &self.candidates[start..end]
+    }
+
+    /// The mods to enable for the next test, `None` once the search is done.
+    pub fn test_set(&self) -> Option<Vec<String>> {
+        if self.done {
+            return None;
+        }
+
+        let chunk = self.chunk(self.index);
+        Some(match self.complement {
+            true => self
+                .candidates
+                .iter()
+                .filter(|id| !chunk.contains(id))
+                .cloned()
+                .collect(),
+            false => chunk.to_vec(),
+        })
+    }
+
+    /// Continues the search with the result of testing [`Ddmin::test_set`].
+    /// The mods which got enabled along with the test set, like its dependencies, are in `enabled` as well.
+    pub fn verdict(&mut self, enabled: &[String], broken: bool) {
+        if self.done {
+            return;
+        }
+
+        // The candidates which were enabled for the test, a dependency pulled in by the set may be the culprit.
+        let tested: Vec<String> = self
+            .candidates
+            .iter()
+            .filter(|id| enabled.contains(id))
+            .cloned()
+            .collect();
+        // When the dependencies enabled every candidate, the test did not narrow anything down.
+        if broken && tested.len() < self.candidates.len() {
+            self.granularity = match self.complement {
+                true => (self.granularity - 1).max(2),
+                false => 2,
+            };
+            self.candidates = tested;
+            self.complement = false;
+            self.index = 0;
+            self.done = self.candidates.len() <= 1;
+            return;
+        }
+
+        self.index += 1;
+        if self.index < self.granularity {
+            return;
+        }
+
+        // With two chunks, the complement of one is just the other chunk.
+        if !self.complement && self.granularity > 2 {
+            self.complement = true;
+            self.index = 0;
+        } else if self.granularity < self.candidates.len() {
+            self.granularity = (self.granularity * 2).min(self.candidates.len());
+            self.complement = false;
+            self.index = 0;
+        } else {
+            self.done = true;
+        }
+    }
+
+    /// The minimal set of mods which still has the issue, once the search is done.
+    pub fn result(&self) -> Option<&[String]> {
+        self.done.then_some(self.candidates.as_slice())
+    }
+
+    pub fn candidates(&self) -> &[String] {
+        &self.candidates
+    }
+}
+
+/// Searches with [`Ddmin`], starting with the mods which were enabled when the issue first showed up.
+#[derive(Default)]
+pub struct DdminSearch {
+    ddmin: Option<Ddmin>,
+}
+
+impl SearchStrategy for DdminSearch {
+    fn propose(&mut self, search: &SearchContext) -> Option<Proposal> {
+        let ddmin = search.advance(&mut self.ddmin, Ddmin::new, |ddmin, last| {
+            ddmin.verdict(&last.enabled, last.broken)
+        })?;
+
+        let mut notification = None;
+        let enabled = match ddmin.result() {
+            Some(result) => {
+                let name = |id: &String| Some(search.plugins.get(id)?.metadata.name.as_str());
+                let names: Vec<&str> = result.iter().filter_map(name).collect();
+                let mut description = format!("The issue happens with {}", names.join(" + "));
+                // The mods get tested along with what they need, so those could take part in the issue as well.
+                let dependencies: Vec<&str> = search
+                    .plugins
+                    .dependency_closure(result)
+                    .iter()
+                    .filter(|id| !result.contains(id))
+                    .filter_map(name)
+                    .collect();
+                if !dependencies.is_empty() {
+                    description += &format!(", which enable their dependencies {}", dependencies.join(", "));
+                }
+                notification = Some(NotificationEvent {
+                    title: "Found the faulty mods".to_string(),
+                    description,
+                    ty: Severity::Info,
+                });
+                result.to_vec()
+            }
+            None => ddmin.test_set().unwrap_or_default(),
+        };
+
+        Some(Proposal {
+            enabled,
+            suspects: ddmin.candidates().to_vec(),
+            notification,
+        })
+    }
+
+    fn estimate(&self, search: &SearchContext) -> Estimate {
+        let (len, first) = match &self.ddmin {
+            Some(ddmin) if ddmin.result().is_some() => (0, 0),
+            Some(ddmin) => (ddmin.candidates().len(), 0),
+            None => (search.splittable().len(), 1),
+        };
+        if len <= 1 {
+            return Estimate {
+                expected: first as f64,
+                worst: Some(first),
+            };
+        }
+
+        // A single faulty mod takes about twice a binary search,
+        // the worst case is the one from the paper.
+        Estimate {
+            expected: (first + 2 * len.ilog2() as usize) as f64,
+            worst: Some(first + (len * len + 3 * len) / 2),
+        }
+    }
+
+    fn result(&self, _search: &SearchContext) -> Option<Vec<String>> {
+        let result = self.ddmin.as_ref()?.result()?;
+        (!result.is_empty()).then(|| result.to_vec())
+    }
+
+    fn progress(&self, _plugins: &PluginList) -> Option<String> {
+        let ddmin = self.ddmin.as_ref()?;
+        Some(match ddmin.result() {
+            Some(result) => format!("Found a set of {} mods", result.len()),
+            None => format!("{} mods left to narrow down", ddmin.candidates().len()),
+        })
+    }
+
+    fn save(&self) -> Value {
+        serde_json::to_value(&self.ddmin).unwrap_or_default()
+    }
+
+    fn load(&mut self, state: &Value) {
+        self.ddmin = serde_json::from_value(state.clone()).unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modpack::search::ddmin::Ddmin;
+
+    /// Runs a search against an issue which only happens if all the culprits are enabled.
+    fn search(mods: usize, culprits: &[&str]) -> (Vec<String>, usize) {
+        search_with_dependencies(mods, culprits, &[])
+    }
+
+    /// Like [`search`], where the first mod of every pair also enables the second one when it is tested.
+    fn search_with_dependencies(mods: usize, culprits: &[&str], dependencies: &[(&str, &str)]) -> (Vec<String>, usize) {
+        let mut ddmin = Ddmin::new((0..mods).map(|i| i.to_string()).collect());
+        let mut tests = 0;
+        while let Some(mut enabled) = ddmin.test_set() {
+            tests += 1;
+            for (dependant, dependency) in dependencies {
+                if enabled.iter().any(|v| v == dependant) && !enabled.iter().any(|v| v == dependency) {
+                    enabled.push(dependency.to_string());
+                }
+            }
+            ddmin.verdict(&enabled, culprits.iter().all(|id| enabled.iter().any(|v| v == id)));
+        }
+
+        let mut result = ddmin.result().unwrap().to_vec();
+        result.sort();
+        (result, tests)
+    }
+
+    #[test]
+    fn minimal_set() {
+        assert_eq!(search(40, &["7"]).0, vec!["7"]);
+        assert_eq!(search(40, &["3", "31"]).0, vec!["3", "31"]);
+        assert_eq!(search(64, &["12", "40", "41"]).0, vec!["12", "40", "41"]);
+        assert_eq!(search(1, &["0"]).0, vec!["0"]);
+
+        // A single culprit is found about as fast as a binary search.
+        assert!(search(64, &["17"]).1 <= 12);
+    }
+    #[test]
+    fn culprit_enabled_as_dependency() {
+        // "30" is never in the same chunk as "5" at first, it only gets tested along with it as a dependency.
+        let (result, _) = search_with_dependencies(40, &["5", "30"], &[("5", "30")]);
+        assert_eq!(result, vec!["30", "5"]);
+        let (result, _) = search_with_dependencies(40, &["12", "30"], &[("5", "30")]);
+        assert_eq!(result, vec!["12", "30"]);
+    }
+}
@@ -0,0 +1,372 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use rand::SeedableRng;
+use rand::prelude::SliceRandom;
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use crate::modpack::closure;
+use crate::modpack::search::{Estimate, Proposal, SearchContext, SearchStrategy};
+use crate::{Plugin, PluginStatus};
+
+/// How many orders of the clusters are tried when splitting.
+const ATTEMPTS: usize = 8;
+
+/// The classic bisection, which keeps half of the mods enabled after the issue showed up,
+/// and swaps the enabled and disabled half when it did not.
+#[derive(Serialize, Deserialize)]
+pub struct Halving {
+    /// Every split with the same seed and step is the same, so a step can be reproduced.
+    seed: u64,
+}
+
+impl Default for Halving {
+    fn default() -> Self {
+        Halving {
+            seed: rand::random(),
+        }
+    }
+}
+
+impl Halving {
+    fn seed(&self, search: &SearchContext) -> u64 {
+        self.seed.wrapping_add(search.history.len() as u64)
+    }
+}
+
+impl SearchStrategy for Halving {
+    fn propose(&mut self, search: &SearchContext) -> Option<Proposal> {
+        let broken = search.last()?.broken;
+        let not_forced = || {
+            search
+                .plugins
+                .list
+                .iter()
+                .filter(|plugin| plugin.forced_status.is_none())
+        };
+
+        if !broken {
+            // Just flips enabled <-> disabled.
+            return Some(Proposal {
+                enabled: not_forced()
+                    .filter(|plugin| plugin.status == PluginStatus::Disabled)
+                    .map(|plugin| plugin.metadata.id.clone())
+                    .collect(),
+                suspects: not_forced()
+                    .filter(|plugin| plugin.status != PluginStatus::NotTheProblem)
+                    .map(|plugin| plugin.metadata.id.clone())
+                    .collect(),
+                notification: None,
+            });
+        }
+
+        let seed = self.seed(search);
+        debug!("Splitting with seed {seed}");
+        let (enabled, to_split) = split(search, seed);
+        Some(Proposal {
+            enabled,
+            suspects: to_split,
+            notification: None,
+        })
+    }
+
+    fn save(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    fn load(&mut self, state: &Value) {
+        // States from before the seed was kept, keep the current one.
+        if let Ok(halving) = serde_json::from_value(state.clone()) {
+            *self = halving;
+        }
+    }
+
+    fn estimate(&self, search: &SearchContext) -> Estimate {
+        let suspects = suspects(search);
+        let len = suspects.len();
+        let enabled = suspects
+            .iter()
+            .filter(|plugin| plugin.status == PluginStatus::Enabled)
+            .count();
+
+        if len <= 1 {
+            return Estimate {
+                expected: 0.0,
+                worst: Some(0),
+            };
+        }
+
+        // Nothing got split yet, so the halves get as uneven as the next split.
+        if enabled == len {
+            let (kept, to_split) = split(search, self.seed(search));
+            let kept = kept.iter().filter(|id| to_split.contains(id)).count();
+            let balance = kept as f64 / len as f64;
+            return Estimate {
+                expected: 1.0 + expected(len, balance),
+                worst: Some(1 + worst(len, balance)),
+            };
+        }
+
+        let disabled = len - enabled;
+        let balance = enabled.max(disabled) as f64 / len as f64;
+        let share = |count: usize| count as f64 / len as f64;
+        Estimate {
+            expected: 1.0
+                + share(enabled) * expected(enabled, balance)
+                + share(disabled) * (1.0 + expected(disabled, balance)),
+            worst: Some(1 + worst(enabled, balance).max(1 + worst(disabled, balance))),
+        }
+    }
+
+    fn result(&self, search: &SearchContext) -> Option<Vec<String>> {
+        match suspects(search).as_slice() {
+            [culprit] => Some(vec![culprit.metadata.id.clone()]),
+            _ => None,
+        }
+    }
+}
+
+/// The mods which could still be faulty.
+fn suspects<'a>(search: &SearchContext<'a>) -> Vec<&'a Plugin> {
+    search
+        .plugins
+        .list
+        .iter()
+        .filter(|plugin| plugin.forced_status.is_none() && plugin.status != PluginStatus::NotTheProblem)
+        .collect()
+}
+
+/// Picks the mods to keep enabled, returning them with the mods which got split.
+fn split(search: &SearchContext, seed: u64) -> (Vec<String>, Vec<String>) {
+    // The disabled mods are not the problem,
+    // because we confirmed that this configuration still contains the issue.
+    let to_split = search.splittable();
+    let enabled = partition(
+        &to_split,
+        |id| search.plugins.dependencies(id),
+        |id| {
+            search
+                .plugins
+                .get(id)
+                .map_or(0, |plugin| plugin.stability())
+        },
+        seed,
+    );
+    (enabled, to_split)
+}
+
+/// Picks the mods to keep enabled, so that about half of the mods are enabled once their dependencies are.
+///
+/// Mods which only load together are kept in one cluster, like a mod with an addon, or a mod with the library only it uses.
+/// Libraries which more mods use are left out of the clusters, they are enabled as long as one of the mods using them is.
+/// The clusters are then added largest first, and the small ones even out the halves.
+/// The same seed always gives the same split.
+fn partition(
+    to_split: &[String],
+    dependencies: impl Fn(&str) -> Vec<String>,
+    stability: impl Fn(&str) -> u32,
+    seed: u64,
+) -> Vec<String> {
+    let index: HashMap<&str, usize> = to_split
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (id.as_str(), i))
+        .collect();
+    let edges: Vec<(usize, usize)> = to_split
+        .iter()
+        .enumerate()
+        .flat_map(|(i, id)| {
+            dependencies(id)
+                .into_iter()
+                .filter_map(|dependency| index.get(dependency.as_str()).map(|j| (i, *j)))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let mut dependants = vec![0; to_split.len()];
+    for (_, j) in &edges {
+        dependants[*j] += 1;
+    }
+
+    let mut parent: Vec<usize> = (0..to_split.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for (i, j) in &edges {
+        if dependants[*j] <= 1 {
+            let (i, j) = (root(&mut parent, *i), root(&mut parent, *j));
+            parent[i] = j;
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<String>> = HashMap::new();
+    for (i, id) in to_split.iter().enumerate() {
+        if dependants[i] <= 1 {
+            let root = root(&mut parent, i);
+            clusters.entry(root).or_default().push(id.clone());
+        }
+    }
+    let mut clusters: Vec<Vec<String>> = clusters.into_values().collect();
+    // The order of a map is random, so it has to be sorted before the seeded shuffle.
+    clusters.sort();
+    let cluster_stability = |cluster: &Vec<String>| cluster.iter().map(|id| stability(id)).max();
+
+    let suspects: HashSet<&String> = to_split.iter().collect();
+    let count = |set: &[String]| set.iter().filter(|id| suspects.contains(id)).count();
+    let target = to_split.len() - to_split.len() / 2;
+    let with = |enabled: &[String], cluster: &[String]| {
+        let mut next = enabled.to_vec();
+        next.extend(cluster.iter().cloned());
+        closure(&next, &dependencies)
+    };
+
+    // Adds the clusters in order as long as they fit in the half.
+    let fill = |clusters: &[Vec<String>]| {
+        let mut enabled: Vec<String> = vec![];
+        let mut left = vec![];
+        for cluster in clusters {
+            if count(&enabled) >= target {
+                break;
+            }
+            if cluster.iter().all(|id| enabled.contains(id)) {
+                continue;
+            }
+
+            let next = with(&enabled, cluster);
+            if count(&next) <= target {
+                enabled = next;
+            } else {
+                left.push(cluster);
+            }
+        }
+
+        // Going over the half is still better than staying far below it.
+        let best = left
+            .into_iter()
+            .map(|cluster| with(&enabled, cluster))
+            .min_by_key(|next| count(next).abs_diff(target));
+        match best {
+            Some(next) if count(&next).abs_diff(target) < count(&enabled).abs_diff(target) => next,
+            _ => enabled,
+        }
+    };
+
+    // The libraries shared by the clusters make it hard to tell which order fits best,
+    // so the largest clusters first is tried along with a few shuffled orders.
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut best: Option<Vec<String>> = None;
+    for attempt in 0..ATTEMPTS {
+        clusters.shuffle(&mut rng);
+        if attempt == 0 {
+            clusters.sort_by_key(|cluster| Reverse((cluster_stability(cluster), cluster.len())));
+        } else {
+            // Stable mods stay enabled.
+            clusters.sort_by_key(|cluster| Reverse(cluster_stability(cluster)));
+        }
+
+        let enabled = fill(&clusters);
+        let better = best
+            .as_ref()
+            .is_none_or(|best| count(&enabled).abs_diff(target) < count(best).abs_diff(target));
+        if better {
+            best = Some(enabled);
+        }
+    }
+
+    best.unwrap_or_default()
+}
+
+/// Splits the mods into the enabled and the disabled part, the enabled one being the bigger one.
+fn halves(len: usize, balance: f64) -> (usize, usize) {
+    let enabled = ((len as f64 * balance).ceil() as usize).clamp(1, len - 1);
+    (enabled, len - enabled)
+}
+
+/// The tests it takes on average to find the faulty mod, once the mods are split.
+/// If the issue does not show up, the halves get swapped, which takes one more test.
+fn expected(len: usize, balance: f64) -> f64 {
+    if len <= 1 {
+        return 0.0;
+    }
+
+    let (enabled, disabled) = halves(len, balance);
+    let share = |count: usize| count as f64 / len as f64;
+    1.0 + share(enabled) * expected(enabled, balance)
+        + share(disabled) * (1.0 + expected(disabled, balance))
+}
+
+fn worst(len: usize, balance: f64) -> usize {
+    if len <= 1 {
+        return 0;
+    }
+
+    let (enabled, disabled) = halves(len, balance);
+    1 + worst(enabled, balance).max(1 + worst(disabled, balance))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::modpack::search::halving::{expected, partition, worst};
+
+    #[test]
+    fn estimate() {
+        assert_eq!(worst(1, 0.5), 0);
+        assert_eq!(worst(2, 0.5), 2);
+        // Uneven halves take longer.
+        assert!(worst(64, 0.8) > worst(64, 0.5));
+        assert!(expected(64, 0.8) > expected(64, 0.5));
+        assert!(expected(64, 0.5) <= worst(64, 0.5) as f64);
+    }
+
+    #[test]
+    fn balanced() {
+        // Addons of one mod, mods with a library only they use, and a library used by everything.
+        let mut dependencies: HashMap<String, Vec<String>> = HashMap::new();
+        let mut mods = vec!["lib".to_string(), "base".to_string()];
+        dependencies.insert("base".to_string(), vec!["lib".to_string()]);
+        for i in 0..8 {
+            let id = format!("addon-{i}");
+            dependencies.insert(id.clone(), vec!["base".to_string(), "lib".to_string()]);
+            mods.push(id);
+        }
+        for i in 0..7 {
+            let id = format!("mod-{i}");
+            dependencies.insert(id.clone(), vec![format!("own-{i}"), "lib".to_string()]);
+            mods.push(id);
+            mods.push(format!("own-{i}"));
+        }
+
+        let split = |seed| {
+            let mut enabled = partition(
+                &mods,
+                |id| dependencies.get(id).cloned().unwrap_or_default(),
+                |_| 0,
+                seed,
+            );
+            enabled.sort();
+            enabled
+        };
+
+        let enabled = split(1);
+        let has = |id: &str| enabled.iter().any(|v| v == id);
+        assert_eq!(enabled.len(), mods.len() / 2);
+        // The dependencies are enabled with the mods needing them.
+        assert!(has("lib"));
+        if (0..8).any(|i| has(&format!("addon-{i}"))) {
+            assert!(has("base"));
+        }
+        for i in 0..7 {
+            assert_eq!(has(&format!("mod-{i}")), has(&format!("own-{i}")));
+        }
+        assert_eq!(split(1), split(1));
+    }
+}
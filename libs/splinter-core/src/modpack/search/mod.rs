@@ -0,0 +1,301 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use bayes::BayesSearch;
+use ddmin::DdminSearch;
+use halving::Halving;
+
+use crate::modpack::{Modpack, PluginList};
+use crate::{NotificationEvent, Plugin, PluginStatus, Severity};
+
+mod bayes;
+mod ddmin;
+mod halving;
+
+/// How the mods get narrowed down after each test.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SearchMode {
+    /// Halves the mods every time, which finds a single faulty mod.
+    #[default]
+    Bisect,
+    /// Delta debugging, which finds a minimal set of mods that only break the game together.
+    Ddmin,
+    /// Keeps the chance of every mod being faulty, for issues which do not show up every time.
+    Bayes,
+}
+
+impl SearchMode {
+    pub fn strategy(&self) -> Box<dyn SearchStrategy> {
+        match self {
+            SearchMode::Bisect => Box::<Halving>::default(),
+            SearchMode::Ddmin => Box::<DdminSearch>::default(),
+            SearchMode::Bayes => Box::<BayesSearch>::default(),
+        }
+    }
+}
+
+/// Decides which mods get tested next.
+pub trait SearchStrategy {
+    /// Proposes the next test after the last verdict in the history, `None` if there is nothing to search.
+    fn propose(&mut self, search: &SearchContext) -> Option<Proposal>;
+
+    /// How many more tests the search needs, counting the test of the mods enabled right now.
+    fn estimate(&self, search: &SearchContext) -> Estimate;
+
+    /// The mods found to cause the issue, `None` until the search is done.
+    fn result(&self, search: &SearchContext) -> Option<Vec<String>>;
+
+    /// A short text on how far the search is.
+    fn progress(&self, _plugins: &PluginList) -> Option<String> {
+        None
+    }
+
+    /// The state of the search, which is kept with every step so undoing also goes back in the search.
+    fn save(&self) -> Value {
+        Value::Null
+    }
+
+    /// Continues from a state returned by [`SearchStrategy::save`], `null` starts over.
+    fn load(&mut self, _state: &Value) {}
+}
+
+pub struct SearchContext<'a> {
+    pub plugins: &'a PluginList,
+    /// Every test of the search so far, the last one is the test the user just answered.
+    pub history: &'a [Verdict],
+    pub config: &'a SearchConfig,
+}
+
+impl SearchContext<'_> {
+    pub fn last(&self) -> Option<&Verdict> {
+        self.history.last()
+    }
+
+    /// The mods which are able to be disabled, the more stable ones first.
+    pub fn splittable(&self) -> Vec<String> {
+        let mut to_split = Vec::new();
+
+        for plugin in self.plugins.iter() {
+            if plugin.should_split() {
+                to_split.push((plugin.metadata.id.clone(), plugin.stability()));
+            }
+        }
+
+        // We sort by the priority.
+        to_split.sort_by(|(_, v0), (_, v1)| v1.cmp(v0));
+        to_split.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Continues a search which keeps its own state with the last verdict, `None` if there is nothing to search.
+    ///
+    /// The first verdict is on the whole modpack, so the state gets started with the mods able to be split
+    /// once the issue showed up with all of them, and every verdict after it goes to the state.
+    pub fn advance<'s, T>(
+        &self,
+        state: &'s mut Option<T>,
+        start: impl FnOnce(Vec<String>) -> T,
+        verdict: impl FnOnce(&mut T, &Verdict),
+    ) -> Option<&'s mut T> {
+        let last = self.last()?;
+        if state.is_none() {
+            return last.broken.then(|| state.insert(start(self.splittable())));
+        }
+
+        let state = state.as_mut()?;
+        verdict(state, last);
+        Some(state)
+    }
+}
+
+/// What the user answered after testing with a set of mods.
+#[derive(Clone, Debug)]
+pub struct Verdict {
+    /// Every mod which was loaded, sorted by id.
+    pub enabled: Vec<String>,
+    pub broken: bool,
+}
+
+/// How many more tests the search likely needs.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub expected: f64,
+    /// `None` if there is no bound, like when the verdicts are not trusted.
+    pub worst: Option<usize>,
+}
+
+/// The next test, mods which are not forced get their status from it.
+#[derive(Default)]
+pub struct Proposal {
+    pub enabled: Vec<String>,
+    /// The mods which could still be faulty, the others are shown as not the problem.
+    pub suspects: Vec<String>,
+    pub notification: Option<NotificationEvent>,
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct SearchConfig {
+    /// How often the issue does not show up even though the faulty mod is enabled.
+    pub false_negative: f64,
+    /// How sure we have to be about a mod to report it.
+    pub confidence: f64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig {
+            false_negative: 0.3,
+            confidence: 0.95,
+        }
+    }
+}
+
+impl Modpack {
+    /// Switches how the mods get narrowed down, the strategy starts over from the mods enabled right now.
+    pub fn set_mode(&mut self, mode: SearchMode) {
+        if self.mode == mode {
+            return;
+        }
+        self.mode = mode;
+        self.strategy = mode.strategy();
+        self.save_session();
+        self.update_estimate();
+    }
+
+    pub fn search_config(&self) -> SearchConfig {
+        self.search_config
+    }
+
+    pub fn set_search_config(&mut self, config: SearchConfig) {
+        self.search_config = config;
+        self.save_session();
+        self.update_estimate();
+    }
+
+    /// Continues the search with the verdict on the mods which are enabled right now.
+    pub(super) fn verdict(&mut self, broken: bool) {
+        if self.is_loading() {
+            return;
+        }
+
+        let mut history = self.history();
+        history.push(Verdict {
+            enabled: self.enabled_ids(|plugin| plugin.status),
+            broken,
+        });
+        let search = SearchContext {
+            plugins: &self.plugins,
+            history: &history,
+            config: &self.search_config,
+        };
+        let Some(proposal) = self.strategy.propose(&search) else {
+            self.notifications.push(NotificationEvent {
+                title: "Nothing to search".to_string(),
+                description: "The issue has to happen with the enabled mods to start searching"
+                    .to_string(),
+                ty: Severity::Info,
+            });
+            return;
+        };
+        debug!(
+            "Testing {} of {} suspects",
+            proposal.enabled.len(),
+            proposal.suspects.len()
+        );
+
+        let enabled: HashSet<String> = proposal.enabled.into_iter().collect();
+        let suspects: HashSet<String> = proposal.suspects.into_iter().collect();
+        for plugin in self.plugins.iter_mut() {
+            if plugin.forced_status.is_some() {
+                continue;
+            }
+
+            let id = &plugin.metadata.id;
+            plugin.status = if enabled.contains(id) {
+                PluginStatus::Enabled
+            } else if suspects.contains(id) {
+                PluginStatus::Disabled
+            } else {
+                PluginStatus::NotTheProblem
+            };
+        }
+
+        self.enable_dependencies();
+        self.disable_breaking();
+        if self.push_changes() {
+            self.notifications.extend(proposal.notification);
+            self.save_state();
+            if let Some(state) = self.undo_queue.last_mut() {
+                state.verdict = Some(broken);
+            }
+            self.save_session();
+        } else {
+            self.reset_search();
+        }
+        self.update_estimate();
+    }
+
+    /// Recomputes how many tests are left, after the statuses or the search changed.
+    pub(super) fn update_estimate(&mut self) {
+        let history = self.history();
+        let search = SearchContext {
+            plugins: &self.plugins,
+            history: &history,
+            config: &self.search_config,
+        };
+        self.estimate = Some(self.strategy.estimate(&search));
+    }
+
+    /// The mods the search found to cause the issue, `None` until it is done.
+    pub fn result(&self) -> Option<Vec<String>> {
+        if self.is_loading() {
+            return None;
+        }
+
+        let history = self.history();
+        let search = SearchContext {
+            plugins: &self.plugins,
+            history: &history,
+            config: &self.search_config,
+        };
+        self.strategy.result(&search)
+    }
+
+    /// The verdicts which led to the current step.
+    fn history(&self) -> Vec<Verdict> {
+        let Some(states) = self.undo_queue.get(..=self.undo_queue_location) else {
+            return vec![];
+        };
+
+        states
+            .windows(2)
+            .filter_map(|states| {
+                Some(Verdict {
+                    enabled: self.enabled_ids(|plugin| {
+                        states[0]
+                            .plugins
+                            .get(&plugin.metadata.id)
+                            .copied()
+                            .unwrap_or(plugin.status)
+                    }),
+                    broken: states[1].verdict?,
+                })
+            })
+            .collect()
+    }
+
+    /// The ids of the mods which would load with the statuses.
+    pub(super) fn enabled_ids(&self, status: impl Fn(&Plugin) -> PluginStatus) -> Vec<String> {
+        let mut enabled: Vec<String> = self
+            .plugins
+            .list
+            .iter()
+            .filter(|plugin| plugin.forced_status.unwrap_or(status(plugin).enabled()))
+            .map(|plugin| plugin.metadata.id.clone())
+            .collect();
+        enabled.sort();
+        enabled
+    }
+}
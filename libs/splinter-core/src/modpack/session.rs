@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+use std::fs::{read_to_string, remove_file};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::modpack::{SearchConfig, SearchMode, State, splinter_dir, write_file};
+
+/// Everything needed to continue a search after splinter was closed,
+/// kept in `.splinter/session.json` next to the mods folder.
+#[derive(Serialize, Deserialize)]
+pub struct Session {
+    #[serde(default)]
+    pub(super) mode: SearchMode,
+    #[serde(default)]
+    pub(super) config: SearchConfig,
+    pub(super) undo_queue: Vec<State>,
+    pub(super) undo_queue_location: usize,
+    /// Only the plugins which are forced, by id.
+    #[serde(default)]
+    pub(super) forced: HashMap<String, bool>,
+    /// Only the plugins which the user gave a stability, by id.
+    #[serde(default)]
+    pub(super) stability_overrides: HashMap<String, u32>,
+}
+
+impl Session {
+    pub fn path(game_dir: &Path) -> PathBuf {
+        splinter_dir(game_dir).join("session.json")
+    }
+
+    pub fn load(game_dir: &Path) -> Option<Session> {
+        let data = read_to_string(Self::path(game_dir)).ok()?;
+        match serde_json::from_str::<Session>(&data) {
+            Ok(session) if !session.undo_queue.is_empty() => Some(session),
+            Ok(_) => None,
+            Err(error) => {
+                warn!("Failed to read session of {game_dir:?}: {error}");
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, game_dir: &Path) -> io::Result<()> {
+        write_file(&Self::path(game_dir), &serde_json::to_vec(self)?)
+    }
+
+    /// Ends the session, the next time the modpack gets opened the search starts from the beginning.
+    pub fn remove(game_dir: &Path) {
+        let path = Self::path(game_dir);
+        if path.exists() {
+            debug!("Removing session {path:?}");
+            if let Err(error) = remove_file(&path) {
+                warn!("Failed to remove session {path:?}: {error}");
+            }
+        }
+    }
+
+    /// The step the user left off at, counting from 1.
+    pub fn step(&self) -> usize {
+        self.undo_queue_location
+            .min(self.undo_queue.len().saturating_sub(1))
+            + 1
+    }
+
+    pub fn steps(&self) -> usize {
+        self.undo_queue.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::PluginStatus;
+    use crate::modpack::{SearchMode, State};
+    use crate::modpack::session::Session;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn round_trip() {
+        let game_dir = TempDir::new("session-round-trip");
+        let state = |status| State {
+            plugins: HashMap::from([("sodium".to_string(), status)]),
+            search: Default::default(),
+            verdict: None,
+            enabled_by: HashMap::new(),
+        };
+
+        Session {
+            mode: SearchMode::Bisect,
+            config: Default::default(),
+            undo_queue: vec![
+                state(PluginStatus::Enabled),
+                state(PluginStatus::NotTheProblem),
+            ],
+            undo_queue_location: 1,
+            forced: HashMap::from([("fabric-api".to_string(), true)]),
+            stability_overrides: HashMap::new(),
+        }
+        .save(&game_dir)
+        .unwrap();
+
+        let session = Session::load(&game_dir).unwrap();
+        assert_eq!((session.step(), session.steps()), (2, 2));
+        assert_eq!(
+            session.undo_queue[1].plugins["sodium"],
+            PluginStatus::NotTheProblem
+        );
+        assert!(session.forced["fabric-api"]);
+
+        Session::remove(&game_dir);
+        assert!(Session::load(&game_dir).is_none());
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::modpack::Modpack;
+use crate::{MAX_STABILITY, PluginMetadata};
+
+/// Libraries which are in a lot of modpacks, and rarely the cause of an issue.
+const KNOWN_LIBRARIES: &[&str] = &[
+    "fabric-api",
+    "fabric-language-kotlin",
+    "kotlinforforge",
+    "cloth-config",
+    "cloth-config2",
+    "architectury",
+    "yet_another_config_lib_v3",
+    "yacl",
+    "owo",
+    "geckolib",
+    "balm",
+    "puzzleslib",
+    "forgeconfigapiport",
+    "resourcefullib",
+    "bookshelf",
+    "moonlight",
+    "collective",
+    "cardinal-components",
+    "trinkets",
+    "curios",
+    "playeranimator",
+    "terrablender",
+    "citadel",
+    "creativecore",
+    "midnightlib",
+    "placeholder-api",
+    "iceberg",
+    "supermartijn642corelib",
+];
+
+/// Scores how unlikely the mod is the faulty one, from 0 to [`MAX_STABILITY`].
+///
+/// Libraries are known ones, or look like they only provide an api,
+/// and the more mods depend on a mod the more it gets tested by other modpacks.
+pub fn stability(metadata: &PluginMetadata, dependants: usize) -> u32 {
+    let mut score = dependants.min(4) as u32 * 10;
+
+    let ids = || std::iter::once(&metadata.id).chain(&metadata.provides);
+    if ids().any(|id| KNOWN_LIBRARIES.contains(&id.as_str())) {
+        score += 50;
+    } else if ids()
+        .chain(std::iter::once(&metadata.name))
+        .any(|id| looks_like_library(id))
+    {
+        score += 20;
+    }
+
+    score.min(MAX_STABILITY)
+}
+
+fn looks_like_library(id: &str) -> bool {
+    let id = id.to_lowercase();
+    id.split(['-', '_', ' '])
+        .any(|part| ["api", "lib", "library", "core", "config"].contains(&part))
+        || id.ends_with("api")
+        || id.ends_with("lib")
+}
+
+impl Modpack {
+    /// Scores every plugin once they are loaded, overrides by the user are kept.
+    pub(super) fn update_stability(&mut self) {
+        let mut dependants: HashMap<String, HashSet<&str>> = HashMap::new();
+        for plugin in self.plugins.iter() {
+            for dependency in &plugin.metadata.depends_on {
+                if let Some(value) = self.plugins.get(&dependency.id) {
+                    if value.metadata.id != plugin.metadata.id {
+                        dependants
+                            .entry(value.metadata.id.clone())
+                            .or_default()
+                            .insert(&plugin.metadata.id);
+                    }
+                }
+            }
+        }
+
+        let scores: Vec<u32> = self
+            .plugins
+            .list
+            .iter()
+            .map(|plugin| {
+                let dependants = dependants.get(&plugin.metadata.id).map_or(0, |v| v.len());
+                stability(&plugin.metadata, dependants)
+            })
+            .collect();
+        for (plugin, score) in self.plugins.list.iter_mut().zip(scores) {
+            plugin.auto_stability = score;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::modpack::stability::stability;
+    use crate::PluginMetadata;
+
+    fn metadata(id: &str, name: &str) -> PluginMetadata {
+        PluginMetadata {
+            id: id.to_string(),
+            provides: vec![],
+            contains: vec![],
+            version: "1.0.0".to_string(),
+            name: name.to_string(),
+            icon: None,
+            depends_on: vec![],
+            breaks: vec![],
+        }
+    }
+
+    #[test]
+    fn libraries() {
+        assert_eq!(stability(&metadata("sodium", "Sodium"), 0), 0);
+        assert_eq!(stability(&metadata("sodium", "Sodium"), 2), 20);
+        assert_eq!(stability(&metadata("fabric-api", "Fabric API"), 10), 90);
+        assert_eq!(stability(&metadata("cobweb", "Cobweb Lib"), 0), 20);
+        assert_eq!(stability(&metadata("mixinextras", "MixinExtras"), 0), 0);
+    }
+}
@@ -0,0 +1,33 @@
+use std::path::Path;
+
+use image::DynamicImage;
+use image::imageops::FilterType;
+
+/// An icon decoded to RGBA pixels, so the app can upload it without decoding it again.
+#[derive(Clone)]
+pub struct IconImage {
+    pub size: [usize; 2],
+    pub rgba: Vec<u8>,
+}
+
+impl IconImage {
+    /// Decodes an icon, scaling it down to `max_size` if it is larger.
+    pub fn decode(data: &[u8], max_size: u32) -> Option<IconImage> {
+        Some(Self::new(image::load_from_memory(data).ok()?, max_size))
+    }
+
+    pub fn open(path: &Path, max_size: u32) -> Option<IconImage> {
+        Some(Self::new(image::open(path).ok()?, max_size))
+    }
+
+    fn new(mut image: DynamicImage, max_size: u32) -> IconImage {
+        if image.width() > max_size || image.height() > max_size {
+            image = image.resize_exact(max_size, max_size, FilterType::Lanczos3);
+        }
+
+        IconImage {
+            size: [image.width() as usize, image.height() as usize],
+            rgba: image.to_rgba8().into_raw(),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::plugin::VersionRange;
+use crate::plugin::metadata::{Dependency, PluginMetadata, read_file, read_inner_jars};
+
+#[derive(Serialize, Deserialize)]
+pub struct FabricMetadata {
+    id: String,
+    provides: Option<Vec<String>>,
+    version: String,
+    name: String,
+    icon: Option<String>,
+    depends: Option<HashMap<String, FabricVersionRange>>,
+    breaks: Option<HashMap<String, FabricVersionRange>>,
+    // Conflicts only make the loader warn, but they are known to cause issues.
+    conflicts: Option<HashMap<String, FabricVersionRange>>,
+    jars: Option<Vec<FabricJarEntry>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum FabricVersionRange {
+    Single(String),
+    // Any of these need to match.
+    Any(Vec<String>),
+}
+
+#[derive(Serialize, Deserialize)]
+struct FabricJarEntry {
+    file: String,
+}
+
+impl FabricMetadata {
+    pub fn read<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> Option<PluginMetadata> {
+        let data = read_file(zip, "fabric.mod.json")?;
+        let json: FabricMetadata = serde_json::from_slice(&data).ok()?;
+
+        let contains = read_inner_jars(zip, json.jars.into_iter().flatten().map(|jar| jar.file));
+
+        let mut depends_on = HashSet::new();
+        Self::add_relations(&json.depends, &mut depends_on);
+
+        let mut breaks = HashSet::new();
+        Self::add_relations(&json.breaks, &mut breaks);
+        Self::add_relations(&json.conflicts, &mut breaks);
+
+        Some(PluginMetadata {
+            id: json.id,
+            provides: json.provides.unwrap_or_default(),
+            contains,
+            version: json.version,
+            name: json.name,
+            icon: json.icon,
+            depends_on: depends_on.into_iter().collect(),
+            breaks: breaks.into_iter().collect(),
+        })
+    }
+
+    fn add_relations(
+        relations: &Option<HashMap<String, FabricVersionRange>>,
+        to: &mut HashSet<Dependency>,
+    ) {
+        for (name, range) in relations.iter().flatten() {
+            let range = match range {
+                FabricVersionRange::Single(range) => VersionRange::parse(range),
+                FabricVersionRange::Any(ranges) => VersionRange::parse_any(ranges),
+            };
+            to.insert(Dependency::new(name.clone(), range));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use zip::ZipArchive;
+
+    use crate::plugin::metadata::PluginMetadata;
+    use crate::plugin::metadata::tests::depends_on;
+    use crate::test_util::jar;
+
+    #[test]
+    fn fabric() {
+        let data = jar(&[(
+            "fabric.mod.json",
+            br#"{
+                "schemaVersion": 1,
+                "id": "example",
+                "version": "1.0.0",
+                "name": "Example",
+                "depends": {
+                    "fabricloader": ">=0.15.0",
+                    "minecraft": ["1.20", "1.20.1"],
+                    "fabric-api": "*"
+                },
+                "breaks": { "optifabric": "*" },
+                "conflicts": { "sodium": "<0.5" }
+            }"#,
+        )]);
+
+        let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+        let metadata = PluginMetadata::new(&mut archive).unwrap();
+        assert_eq!(
+            depends_on(&metadata),
+            vec!["fabric-api", "fabricloader", "minecraft"]
+        );
+
+        let minecraft = metadata
+            .depends_on
+            .iter()
+            .find(|dependency| dependency.id == "minecraft")
+            .unwrap();
+        assert!(minecraft.version.matches("1.20.1"));
+        assert!(!minecraft.version.matches("1.20.2"));
+
+        let mut breaks: Vec<&str> = metadata.breaks.iter().map(|v| v.id.as_str()).collect();
+        breaks.sort();
+        assert_eq!(breaks, vec!["optifabric", "sodium"]);
+    }
+}
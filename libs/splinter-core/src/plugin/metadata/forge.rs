@@ -0,0 +1,237 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::plugin::VersionRange;
+use crate::plugin::metadata::{
+    Dependency, PluginMetadata, manifest_attribute, read_file, read_inner_jars,
+};
+
+/// Both Forge and NeoForge describe their mods in a `mods.toml`, NeoForge just moved it to another file name.
+const MODS_TOML: [&str; 2] = ["META-INF/neoforge.mods.toml", "META-INF/mods.toml"];
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForgeMetadata {
+    #[serde(default)]
+    mods: Vec<ForgeMod>,
+    #[serde(default)]
+    dependencies: HashMap<String, Vec<ForgeDependency>>,
+    // Older mods define the logo for the whole file instead of per mod.
+    logo_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeMod {
+    mod_id: String,
+    version: Option<String>,
+    display_name: Option<String>,
+    logo_file: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForgeDependency {
+    mod_id: String,
+    // Forge uses mandatory, NeoForge and newer Forge versions use type.
+    mandatory: Option<bool>,
+    #[serde(rename = "type")]
+    ty: Option<String>,
+    version_range: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JarJarMetadata {
+    #[serde(default)]
+    jars: Vec<JarJarEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JarJarEntry {
+    path: String,
+}
+
+impl ForgeMetadata {
+    pub fn read<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> Option<PluginMetadata> {
+        let data = MODS_TOML.iter().find_map(|name| read_file(zip, name))?;
+        let toml: ForgeMetadata = toml::from_str(std::str::from_utf8(&data).ok()?).ok()?;
+
+        let mut mods = toml.mods.into_iter();
+        let primary = mods.next()?;
+        // Every other mod in this file gets loaded together with the primary one.
+        let provides: Vec<String> = mods.map(|value| value.mod_id).collect();
+
+        let mut depends_on = HashSet::new();
+        let mut breaks = HashSet::new();
+        for (mod_id, dependencies) in &toml.dependencies {
+            if mod_id != &primary.mod_id && !provides.contains(mod_id) {
+                continue;
+            }
+
+            for dependency in dependencies {
+                if dependency.mod_id == primary.mod_id || provides.contains(&dependency.mod_id) {
+                    continue;
+                }
+
+                let range = dependency
+                    .version_range
+                    .as_deref()
+                    .map(VersionRange::parse_maven)
+                    .unwrap_or_else(VersionRange::any);
+                if dependency.incompatible() {
+                    breaks.insert(Dependency::new(dependency.mod_id.clone(), range));
+                } else if dependency.required() {
+                    depends_on.insert(Dependency::new(dependency.mod_id.clone(), range));
+                }
+            }
+        }
+
+        let contains = match read_file(zip, "META-INF/jarjar/metadata.json")
+            .and_then(|data| serde_json::from_slice::<JarJarMetadata>(&data).ok())
+        {
+            Some(jarjar) => read_inner_jars(zip, jarjar.jars.into_iter().map(|jar| jar.path)),
+            None => vec![],
+        };
+
+        let version = match primary.version {
+            // The version gets filled in from the manifest at runtime.
+            Some(version) if version == "${file.jarVersion}" => {
+                manifest_attribute(zip, "Implementation-Version").unwrap_or(version)
+            }
+            Some(version) => version,
+            None => manifest_attribute(zip, "Implementation-Version").unwrap_or_default(),
+        };
+
+        Some(PluginMetadata {
+            name: primary
+                .display_name
+                .unwrap_or_else(|| primary.mod_id.clone()),
+            id: primary.mod_id,
+            provides,
+            contains,
+            version,
+            icon: primary.logo_file.or(toml.logo_file),
+            depends_on: depends_on.into_iter().collect(),
+            breaks: breaks.into_iter().collect(),
+        })
+    }
+}
+
+impl ForgeDependency {
+    fn required(&self) -> bool {
+        match &self.ty {
+            Some(ty) => ty.eq_ignore_ascii_case("required"),
+            None => self.mandatory.unwrap_or(false),
+        }
+    }
+
+    fn incompatible(&self) -> bool {
+        matches!(&self.ty, Some(ty) if ty.eq_ignore_ascii_case("incompatible"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use zip::ZipArchive;
+
+    use crate::plugin::metadata::PluginMetadata;
+    use crate::plugin::metadata::tests::depends_on;
+    use crate::test_util::jar;
+
+    #[test]
+    fn forge() {
+        let inner = jar(&[(
+            "META-INF/mods.toml",
+            br#"
+            modLoader = "javafml"
+            loaderVersion = "[47,)"
+            [[mods]]
+            modId = "inner"
+            version = "1.0"
+            "#,
+        )]);
+        let outer = jar(&[
+            (
+                "META-INF/neoforge.mods.toml",
+                br#"
+                modLoader = "javafml"
+                loaderVersion = "[1,)"
+                license = "MIT"
+                logoFile = "logo.png"
+
+                [[mods]]
+                modId = "example"
+                version = "${file.jarVersion}"
+                displayName = "Example"
+
+                [[mods]]
+                modId = "example_addon"
+
+                [[dependencies.example]]
+                modId = "neoforge"
+                type = "required"
+                versionRange = "[20.4,)"
+
+                [[dependencies.example]]
+                modId = "jei"
+                type = "optional"
+
+                [[dependencies.example]]
+                modId = "optifine"
+                type = "incompatible"
+                versionRange = "[,1.20.4_HD_U_I7)"
+
+                [[dependencies.example_addon]]
+                modId = "example"
+                mandatory = true
+
+                [[dependencies.example_addon]]
+                modId = "curios"
+                mandatory = true
+
+                [[dependencies.unrelated]]
+                modId = "other"
+                mandatory = true
+                "#,
+            ),
+            (
+                "META-INF/MANIFEST.MF",
+                b"Manifest-Version: 1.0\r\nImplementation-Version: 3.1.4\r\n",
+            ),
+            (
+                "META-INF/jarjar/metadata.json",
+                br#"{ "jars": [{ "identifier": { "group": "org.example", "artifact": "inner" }, "path": "META-INF/jarjar/inner.jar" }] }"#,
+            ),
+            ("META-INF/jarjar/inner.jar", &inner),
+        ]);
+
+        let mut archive = ZipArchive::new(Cursor::new(outer)).unwrap();
+        let metadata = PluginMetadata::new(&mut archive).unwrap();
+        assert_eq!(metadata.id, "example");
+        assert_eq!(metadata.name, "Example");
+        assert_eq!(metadata.version, "3.1.4");
+        assert_eq!(metadata.icon.as_deref(), Some("logo.png"));
+        assert_eq!(metadata.provides, vec!["example_addon"]);
+
+        assert_eq!(depends_on(&metadata), vec!["curios", "neoforge"]);
+        assert_eq!(metadata.breaks.len(), 1);
+        assert_eq!(metadata.breaks[0].id, "optifine");
+
+        let neoforge = metadata
+            .depends_on
+            .iter()
+            .find(|dependency| dependency.id == "neoforge")
+            .unwrap();
+        assert!(neoforge.version.matches("20.4.237"));
+        assert!(!neoforge.version.matches("20.2.88"));
+
+        assert_eq!(metadata.contains.len(), 1);
+        assert_eq!(metadata.contains[0].id, "inner");
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use std::io;
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::plugin::VersionRange;
+use crate::plugin::metadata::{Dependency, PluginMetadata, manifest_attribute, read_file};
+
+/// The `mcmod.info` of Forge 1.12.2 and older, which is either a plain list or a versioned `modList`.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LegacyForgeMetadata {
+    List(Vec<LegacyForgeMod>),
+    Versioned {
+        #[serde(rename = "modList")]
+        mod_list: Vec<LegacyForgeMod>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyForgeMod {
+    #[serde(default)]
+    modid: String,
+    name: Option<String>,
+    version: Option<String>,
+    logo_file: Option<String>,
+    // Entries look like "modid" or "modid@[1.0,)".
+    #[serde(default)]
+    required_mods: Vec<String>,
+}
+
+impl LegacyForgeMetadata {
+    pub fn read<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> Option<PluginMetadata> {
+        let data = read_file(zip, "mcmod.info")?;
+        let json: LegacyForgeMetadata = serde_json::from_slice(&data).ok()?;
+        let mods = match json {
+            LegacyForgeMetadata::List(mods) | LegacyForgeMetadata::Versioned { mod_list: mods } => {
+                mods
+            }
+        };
+
+        let mut mods = mods.into_iter();
+        let primary = mods.next()?;
+        let provides: Vec<String> = mods
+            .map(|value| value.modid)
+            .filter(|id| !id.is_empty())
+            .collect();
+
+        let mut depends_on = HashSet::new();
+        for required in &primary.required_mods {
+            let id = required.split('@').next().unwrap_or(required).trim();
+            if !id.is_empty() && id != primary.modid && !provides.iter().any(|value| value == id) {
+                depends_on.insert(Dependency::new(id, VersionRange::any()));
+            }
+        }
+
+        // Build scripts often forget to fill in the version.
+        let version = match primary.version {
+            Some(version) if !version.contains("${") => version,
+            _ => manifest_attribute(zip, "Implementation-Version").unwrap_or_default(),
+        };
+
+        Some(PluginMetadata {
+            // Left empty if missing, the file name will be used instead.
+            name: primary.name.unwrap_or_default(),
+            id: primary.modid,
+            provides,
+            contains: vec![],
+            version,
+            icon: primary
+                .logo_file
+                .map(|logo| logo.trim_start_matches('/').to_string())
+                .filter(|logo| !logo.is_empty()),
+            depends_on: depends_on.into_iter().collect(),
+            breaks: vec![],
+        })
+    }
+
+    /// Coremods are only identified by the `FMLCorePlugin` attribute in their manifest.
+    pub fn is_coremod<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> bool {
+        manifest_attribute(zip, "FMLCorePlugin").is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use zip::ZipArchive;
+
+    use crate::plugin::metadata::PluginMetadata;
+    use crate::plugin::metadata::tests::depends_on;
+    use crate::test_util::jar;
+
+    #[test]
+    fn mcmod_info() {
+        let data = jar(&[(
+            "mcmod.info",
+            br#"{
+                "modListVersion": 2,
+                "modList": [{
+                    "modid": "example",
+                    "name": "Example",
+                    "version": "1.12.2-1.0",
+                    "logoFile": "/assets/example/logo.png",
+                    "requiredMods": ["Forge@[14.23,)", "baubles"]
+                }]
+            }"#,
+        )]);
+
+        let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+        let metadata = PluginMetadata::new_or_file_name(&mut archive, "Example-1.0.jar");
+        assert_eq!(metadata.id, "example");
+        assert_eq!(metadata.name, "Example");
+        assert_eq!(metadata.version, "1.12.2-1.0");
+        assert_eq!(metadata.icon.as_deref(), Some("assets/example/logo.png"));
+
+        assert_eq!(depends_on(&metadata), vec!["Forge", "baubles"]);
+    }
+
+    #[test]
+    fn coremod() {
+        let data = jar(&[(
+            "META-INF/MANIFEST.MF",
+            b"Manifest-Version: 1.0\nFMLCorePlugin: org.example.LoadingPlugin\nImplementation-Version: 2.3\n",
+        )]);
+
+        let mut archive = ZipArchive::new(Cursor::new(data)).unwrap();
+        let metadata = PluginMetadata::new_or_file_name(&mut archive, "Fast Core-2.3.jar.disabled");
+        assert_eq!(metadata.id, "fast_core_2_3");
+        assert_eq!(metadata.name, "Fast Core-2.3");
+        assert_eq!(metadata.version, "2.3");
+    }
+}
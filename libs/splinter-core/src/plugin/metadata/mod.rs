@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+use std::io;
+use std::io::{Cursor, Read};
+
+use tracing::{debug, warn};
+use zip::ZipArchive;
+
+use crate::plugin::VersionRange;
+use fabric::FabricMetadata;
+use forge::ForgeMetadata;
+use legacy::LegacyForgeMetadata;
+use quilt::QuiltMetadata;
+
+mod fabric;
+mod forge;
+mod legacy;
+mod quilt;
+
+pub type PluginId = String;
+
+/// A mod which needs to be present for a plugin to load.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Dependency {
+    pub id: PluginId,
+    pub version: VersionRange,
+}
+
+impl Dependency {
+    pub fn new(id: impl Into<PluginId>, version: VersionRange) -> Dependency {
+        Dependency {
+            id: id.into(),
+            version,
+        }
+    }
+}
+
+pub struct PluginMetadata {
+    pub id: PluginId,
+    // Some mods like fabric api provide multiple ids to be backwards compatible
+    pub provides: Vec<PluginId>,
+    // This is a list of mods which are bundled within this plugin.
+    pub contains: Vec<PluginMetadata>,
+    pub version: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub depends_on: Vec<Dependency>,
+    // Mods which cannot be loaded together with this one.
+    pub breaks: Vec<Dependency>,
+}
+
+impl PluginMetadata {
+    pub fn new<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> Option<PluginMetadata> {
+        let mut metadata = FabricMetadata::read(zip)
+            .or_else(|| QuiltMetadata::read(zip))
+            .or_else(|| ForgeMetadata::read(zip))
+            .or_else(|| LegacyForgeMetadata::read(zip))?;
+
+        let mut depends_on = HashSet::new();
+        let mut breaks = HashSet::new();
+        Self::add_module_relations(&metadata, &mut depends_on, &mut breaks);
+        metadata.depends_on = depends_on.into_iter().collect();
+        metadata.breaks = breaks.into_iter().collect();
+        Some(metadata)
+    }
+
+    /// Reads the metadata like [`PluginMetadata::new`],
+    /// but falls back to an id derived from the file name if the jar does not declare a usable one.
+    pub fn new_or_file_name<R: Read + io::Seek>(
+        zip: &mut ZipArchive<R>,
+        file_name: &str,
+    ) -> PluginMetadata {
+        let mut metadata = match Self::new(zip) {
+            Some(metadata) => metadata,
+            None => {
+                if LegacyForgeMetadata::is_coremod(zip) {
+                    debug!("{file_name} is a coremod without mod metadata");
+                } else {
+                    debug!("{file_name} does not have metadata, identifying it by its file name");
+                }
+
+                PluginMetadata {
+                    id: String::new(),
+                    provides: vec![],
+                    contains: vec![],
+                    version: manifest_attribute(zip, "Implementation-Version").unwrap_or_default(),
+                    name: String::new(),
+                    icon: None,
+                    depends_on: vec![],
+                    breaks: vec![],
+                }
+            }
+        };
+
+        metadata.fill_from_file_name(file_name);
+        metadata
+    }
+
+    /// Derives the id and name from the file name if the metadata did not declare them.
+    fn fill_from_file_name(&mut self, file_name: &str) {
+        let file_name = file_name.rsplit('/').next().unwrap_or(file_name);
+        // Strip the .jar and any of our .disabled/.tempdisabled extensions.
+        let stem = match file_name.find(".jar") {
+            Some(index) => &file_name[..index],
+            None => file_name,
+        };
+
+        if self.id.trim().is_empty() {
+            self.id = stem
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+        }
+        if self.name.trim().is_empty() {
+            self.name = stem.to_string();
+        }
+    }
+
+    /// Finds the version of the module with this id, which may be nested within this plugin.
+    pub fn module_version(&self, id: &str) -> Option<&str> {
+        if self.id == id || self.provides.iter().any(|provides| provides == id) {
+            return Some(&self.version);
+        }
+
+        self.contains
+            .iter()
+            .find_map(|metadata| metadata.module_version(id))
+    }
+
+    fn add_module_relations(
+        metadata: &PluginMetadata,
+        depends_on: &mut HashSet<Dependency>,
+        breaks: &mut HashSet<Dependency>,
+    ) {
+        for on in &metadata.depends_on {
+            depends_on.insert(on.clone());
+        }
+        for on in &metadata.breaks {
+            breaks.insert(on.clone());
+        }
+
+        for plugin in &metadata.contains {
+            Self::add_module_relations(plugin, depends_on, breaks);
+        }
+    }
+}
+
+/// Reads a whole file out of the archive.
+fn read_file<R: Read + io::Seek>(zip: &mut ZipArchive<R>, name: &str) -> Option<Vec<u8>> {
+    let mut file = zip.by_name(name).ok()?;
+    let mut data = Vec::new();
+    file.read_to_end(&mut data).ok()?;
+    Some(data)
+}
+
+/// Reads a main attribute out of the jar manifest.
+fn manifest_attribute<R: Read + io::Seek>(zip: &mut ZipArchive<R>, key: &str) -> Option<String> {
+    let data = read_file(zip, "META-INF/MANIFEST.MF")?;
+    String::from_utf8_lossy(&data).lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim().to_string())
+    })
+}
+
+/// Reads the metadata of every jar nested within this archive.
+fn read_inner_jars<R: Read + io::Seek>(
+    zip: &mut ZipArchive<R>,
+    jars: impl IntoIterator<Item = String>,
+) -> Vec<PluginMetadata> {
+    let mut contains = Vec::new();
+    for jar in jars {
+        debug!("Loading inner mod {jar}");
+        let Some(file_data) = read_file(zip, &jar) else {
+            warn!("Inner jar {jar} does not exist");
+            continue;
+        };
+
+        let mut reader = Cursor::new(file_data.as_slice());
+        let metadata = ZipArchive::new(&mut reader)
+            .ok()
+            .and_then(|mut archive| PluginMetadata::new(&mut archive))
+            .map(|mut metadata| {
+                metadata.fill_from_file_name(&jar);
+                metadata
+            });
+        if let Some(metadata) = metadata {
+            contains.push(metadata);
+        } else {
+            warn!("Failed to read inner jar {jar}");
+        }
+    }
+    contains
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::plugin::metadata::PluginMetadata;
+
+    /// The sorted dependency ids, as the parsers do not keep the declaration order.
+    pub fn depends_on(metadata: &PluginMetadata) -> Vec<&str> {
+        let mut depends_on: Vec<&str> = metadata
+            .depends_on
+            .iter()
+            .map(|dependency| dependency.id.as_str())
+            .collect();
+        depends_on.sort();
+        depends_on
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::io::Read;
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::plugin::metadata::{Dependency, PluginMetadata, read_file, read_inner_jars};
+use crate::plugin::{ICON_SIZE, VersionRange};
+
+#[derive(Serialize, Deserialize)]
+pub struct QuiltMetadata {
+    quilt_loader: QuiltLoader,
+}
+
+#[derive(Serialize, Deserialize)]
+struct QuiltLoader {
+    id: String,
+    version: String,
+    #[serde(default)]
+    provides: Vec<QuiltProvides>,
+    #[serde(default)]
+    depends: Vec<QuiltDependency>,
+    #[serde(default)]
+    breaks: Vec<QuiltDependency>,
+    #[serde(default)]
+    jars: Vec<String>,
+    metadata: Option<QuiltDisplayMetadata>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum QuiltProvides {
+    Id(String),
+    Object { id: String },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum QuiltDependency {
+    Id(String),
+    Object {
+        id: String,
+        versions: Option<QuiltVersions>,
+        #[serde(default)]
+        optional: bool,
+    },
+    // A list of dependencies of which any one needs to be present.
+    Any(Vec<QuiltDependency>),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum QuiltVersions {
+    Single(String),
+    // Any of these need to match.
+    Any(Vec<String>),
+    // The object form combining "any" and "all" is not understood yet, it is treated as any version.
+    Other(serde_json::Value),
+}
+
+#[derive(Serialize, Deserialize)]
+struct QuiltDisplayMetadata {
+    name: Option<String>,
+    icon: Option<QuiltIcon>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum QuiltIcon {
+    Path(String),
+    // Icons per size in pixels.
+    Sized(HashMap<String, String>),
+}
+
+impl QuiltMetadata {
+    pub fn read<R: Read + io::Seek>(zip: &mut ZipArchive<R>) -> Option<PluginMetadata> {
+        let data = read_file(zip, "quilt.mod.json")?;
+        let json: QuiltMetadata = serde_json::from_slice(&data).ok()?;
+        let loader = json.quilt_loader;
+
+        let contains = read_inner_jars(zip, loader.jars);
+
+        let mut depends_on = HashSet::new();
+        for dependency in &loader.depends {
+            dependency.required(&mut depends_on);
+        }
+
+        let mut breaks = HashSet::new();
+        for dependency in &loader.breaks {
+            dependency.all(&mut breaks);
+        }
+
+        let (name, icon) = match loader.metadata {
+            Some(metadata) => (metadata.name, metadata.icon.and_then(QuiltIcon::pick)),
+            None => (None, None),
+        };
+
+        Some(PluginMetadata {
+            name: name.unwrap_or_else(|| loader.id.clone()),
+            id: loader.id,
+            provides: loader
+                .provides
+                .into_iter()
+                .map(|provides| match provides {
+                    QuiltProvides::Id(id) | QuiltProvides::Object { id } => id,
+                })
+                .collect(),
+            contains,
+            version: loader.version,
+            icon,
+            depends_on: depends_on.into_iter().collect(),
+            breaks: breaks.into_iter().collect(),
+        })
+    }
+}
+
+impl QuiltDependency {
+    /// Collects the dependencies which have to be present for the mod to load.
+    fn required(&self, depends_on: &mut HashSet<Dependency>) {
+        match self {
+            QuiltDependency::Id(id) => {
+                depends_on.insert(Dependency::new(id, VersionRange::any()));
+            }
+            QuiltDependency::Object {
+                id,
+                versions,
+                optional,
+            } => {
+                if !optional {
+                    depends_on.insert(Dependency::new(id, QuiltVersions::range(versions)));
+                }
+            }
+            // Dependencies only name a single mod, so "one of" is only kept when every option needs the same mod.
+            // Pinning one of the options would disable the mod while another option is installed.
+            QuiltDependency::Any(options) => {
+                let mut options = options.iter().map(QuiltDependency::single);
+                let Some(Some(mut dependency)) = options.next() else {
+                    return;
+                };
+                for option in options {
+                    match option {
+                        Some(other) if other.id == dependency.id => {
+                            // The ranges cannot be joined, so any version of the mod is accepted.
+                            if other.version != dependency.version {
+                                dependency.version = VersionRange::any();
+                            }
+                        }
+                        _ => return,
+                    }
+                }
+                depends_on.insert(dependency);
+            }
+        }
+    }
+
+    /// The one dependency which is required by this declaration, if it requires exactly one.
+    fn single(&self) -> Option<Dependency> {
+        let mut required = HashSet::new();
+        self.required(&mut required);
+        if required.len() != 1 {
+            return None;
+        }
+        required.into_iter().next()
+    }
+
+    /// Collects every relation mentioned in this declaration.
+    fn all(&self, to: &mut HashSet<Dependency>) {
+        match self {
+            QuiltDependency::Id(id) => {
+                to.insert(Dependency::new(id, VersionRange::any()));
+            }
+            QuiltDependency::Object { id, versions, .. } => {
+                to.insert(Dependency::new(id, QuiltVersions::range(versions)));
+            }
+            QuiltDependency::Any(options) => {
+                for option in options {
+                    option.all(to);
+                }
+            }
+        }
+    }
+}
+
+impl QuiltVersions {
+    fn range(versions: &Option<QuiltVersions>) -> VersionRange {
+        match versions {
+            Some(QuiltVersions::Single(range)) => VersionRange::parse(range),
+            Some(QuiltVersions::Any(ranges)) => VersionRange::parse_any(ranges),
+            Some(QuiltVersions::Other(_)) | None => VersionRange::any(),
+        }
+    }
+}
+
+impl QuiltIcon {
+    fn pick(self) -> Option<String> {
+        match self {
+            QuiltIcon::Path(path) => Some(path),
+            QuiltIcon::Sized(sizes) => {
+                // We prefer the smallest icon that is still at least as large as what we display.
+                let mut sizes: Vec<(u32, String)> = sizes
+                    .into_iter()
+                    .filter_map(|(size, path)| Some((size.parse().ok()?, path)))
+                    .collect();
+                sizes.sort_by_key(|(size, _)| *size);
+                let index = sizes
+                    .iter()
+                    .position(|(size, _)| *size >= ICON_SIZE)
+                    .unwrap_or(sizes.len().checked_sub(1)?);
+                Some(sizes.swap_remove(index).1)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use zip::ZipArchive;
+
+    use crate::plugin::metadata::PluginMetadata;
+    use crate::plugin::metadata::tests::depends_on;
+    use crate::test_util::jar;
+
+    #[test]
+    fn quilt() {
+        let inner = jar(&[(
+            "quilt.mod.json",
+            br#"{ "quilt_loader": { "id": "inner", "version": "1.0", "depends": ["library"] } }"#,
+        )]);
+        let outer = jar(&[
+            (
+                "quilt.mod.json",
+                br#"{
+                    "schema_version": 1,
+                    "quilt_loader": {
+                        "group": "org.example",
+                        "id": "example",
+                        "version": "2.0.0",
+                        "provides": ["old_example", { "id": "other_example", "version": "2.0.0" }],
+                        "depends": [
+                            "quilt_loader",
+                            { "id": "minecraft", "versions": ">=1.20" },
+                            { "id": "modmenu", "optional": true },
+                            [{ "id": "fabric_api" }, { "id": "qsl" }],
+                            [{ "id": "config", "versions": "1.x" }, { "id": "config", "versions": "2.x" }]
+                        ],
+                        "breaks": [{ "id": "optifabric" }, "sodium"],
+                        "jars": ["META-INF/jars/inner.jar"],
+                        "metadata": { "name": "Example", "icon": { "16": "small.png", "64": "large.png" } }
+                    }
+                }"#,
+            ),
+            ("META-INF/jars/inner.jar", &inner),
+        ]);
+
+        let mut archive = ZipArchive::new(Cursor::new(outer)).unwrap();
+        let metadata = PluginMetadata::new(&mut archive).unwrap();
+        assert_eq!(metadata.id, "example");
+        assert_eq!(metadata.name, "Example");
+        assert_eq!(metadata.version, "2.0.0");
+        assert_eq!(metadata.icon.as_deref(), Some("large.png"));
+        assert_eq!(metadata.provides, vec!["old_example", "other_example"]);
+
+        assert_eq!(
+            depends_on(&metadata),
+            vec!["config", "library", "minecraft", "quilt_loader"]
+        );
+        // Either version of the config library will do.
+        let config = metadata.depends_on.iter().find(|dependency| dependency.id == "config").unwrap();
+        assert!(config.version.is_any());
+
+        let mut breaks: Vec<&str> = metadata.breaks.iter().map(|v| v.id.as_str()).collect();
+        breaks.sort();
+        assert_eq!(breaks, vec!["optifabric", "sodium"]);
+
+        assert_eq!(metadata.contains.len(), 1);
+        assert_eq!(metadata.contains[0].id, "inner");
+        assert_eq!(metadata.contains[0].name, "inner");
+    }
+}
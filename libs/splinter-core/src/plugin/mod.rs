@@ -0,0 +1,184 @@
+use std::fs::read;
+use std::io;
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+
+use sha1::{Digest, Sha1};
+use tracing::{debug, info, warn};
+use zip::ZipArchive;
+
+pub use icon::IconImage;
+pub use metadata::{Dependency, PluginMetadata};
+pub use status::PluginStatus;
+pub use version::VersionRange;
+
+mod icon;
+mod metadata;
+mod status;
+mod version;
+
+/// The size the icons of the mods get scaled down to.
+pub const ICON_SIZE: u32 = 32;
+pub const MAX_STABILITY: u32 = 100;
+
+pub struct Plugin {
+    pub metadata: PluginMetadata,
+    path: PathBuf,
+    icon: Option<IconImage>,
+    size: u64,
+    /// The hash mod sites like Modrinth look versions up by.
+    sha1: String,
+
+    /// The stability makes the plugin be less often split,
+    /// Plugins that are stable are libraries which are often present in a mod configuration and are known to be quite stable.
+    pub auto_stability: u32,
+    /// The stability the user picked instead of the automatic one.
+    pub stability_override: Option<u32>,
+    pub forced_status: Option<bool>,
+
+    pub status: PluginStatus,
+    file_status: FileStatus,
+}
+
+#[derive(Copy, Clone)]
+enum FileStatus {
+    Enabled,
+    ForceDisabled,
+}
+impl Plugin {
+    /// Reads the mod at the path, `None` if the file is not a mod.
+    /// Fails if it is a mod which could not be read, like a jar which did not finish downloading.
+    ///
+    /// The file does not get renamed, a jar disabled by a search is loaded as disabled.
+    pub fn new(path: PathBuf) -> io::Result<Option<Plugin>> {
+        let Some(extension) = path.extension().and_then(|v| v.to_str()) else {
+            return Ok(None);
+        };
+        let (file_status, status) = match extension {
+            "jar" => (FileStatus::Enabled, PluginStatus::Enabled),
+            "disabled" => (FileStatus::ForceDisabled, PluginStatus::Disabled),
+            "tempdisabled" => (FileStatus::Enabled, PluginStatus::Disabled),
+            _ => {
+                info!("Unknown file extension \"{extension}\" in mods folder");
+                return Ok(None);
+            }
+        };
+        debug!("Loading mod at {path:?}");
+        // The jar is read once, for both its hash and its contents.
+        let data = read(&path)?;
+        let size = data.len() as u64;
+        let sha1 = format!("{:x}", Sha1::digest(&data));
+        let mut archive = ZipArchive::new(Cursor::new(data))?;
+
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let metadata = PluginMetadata::new_or_file_name(&mut archive, &file_name);
+
+        Ok(Some(Plugin {
+            icon: metadata
+                .icon
+                .as_ref()
+                .and_then(|icon| Self::load_icon(&mut archive, icon)),
+            metadata,
+            size,
+            sha1,
+            auto_stability: 0,
+            stability_override: None,
+            forced_status: if matches!(file_status, FileStatus::ForceDisabled) { Some(false)} else  { None},
+            status,
+            file_status,
+            path,
+        }))
+    }
+
+    /// The path the file should have for its current status, `None` if it is no longer a jar.
+    pub fn target_path(&self) -> Option<PathBuf> {
+        let file = match self.file_status {
+            FileStatus::Enabled => {
+                let mut path = self.path.clone();
+                let mut is_jar = false;
+                while let Some(extension) =  path.extension() {
+                    let extension = extension.to_str().unwrap();
+                    if extension == "jar" {
+                        is_jar = true;
+                        break;
+                    }
+                    path = path.with_extension("");
+                }
+
+                if !is_jar {
+                    warn!("Plugin at {:?} is no longer a jar.", self.path);
+                    return None;
+                }
+
+                path
+            }
+            FileStatus::ForceDisabled => {
+                self.path.with_extension("")
+            }
+        };
+
+        Some(match self.forced_status {
+            None => {
+                match self.status {
+                    PluginStatus::Enabled => {
+                        file
+                    }
+                    PluginStatus::NotTheProblem | PluginStatus::Disabled => {
+                        file.with_extension("jar.tempdisabled")
+                    }
+                }
+            }
+            Some(value) => {
+                if value {
+                    file
+                } else {
+                    file.with_extension("jar.disabled")
+                }
+            }
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Called once the file got renamed.
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    /// If the plugin is going to be loaded by the game.
+    pub fn enabled(&self) -> bool {
+        self.forced_status.unwrap_or(self.status.enabled())
+    }
+
+    pub fn stability(&self) -> u32 {
+        self.stability_override.unwrap_or(self.auto_stability)
+    }
+
+    pub fn should_split(&self) -> bool {
+        self.forced_status.is_none() && self.status.enabled()
+    }
+
+    /// The icon of the mod, already scaled down.
+    pub fn icon(&self) -> Option<&IconImage> {
+        self.icon.as_ref()
+    }
+
+    /// The size of the jar in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The SHA-1 of the jar, read when the plugin got loaded.
+    pub fn sha1(&self) -> &str {
+        &self.sha1
+    }
+
+    fn load_icon<R: Read + Seek>(archive: &mut ZipArchive<R>, icon: &str) -> Option<IconImage> {
+        let mut file = archive.by_name(icon).ok()?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).ok()?;
+        IconImage::decode(&data, ICON_SIZE)
+    }
+}
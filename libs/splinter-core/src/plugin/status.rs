@@ -1,4 +1,6 @@
-#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Ord, PartialOrd, Eq, PartialEq, Hash, Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum PluginStatus {
     Enabled = 0,
     Disabled = 1,
@@ -13,6 +15,14 @@ impl PluginStatus {
         }
     }
 
+    pub fn name(&self) -> &'static str {
+        match self {
+            PluginStatus::Enabled => "Enabled",
+            PluginStatus::Disabled => "Disabled",
+            PluginStatus::NotTheProblem => "Not faulty",
+        }
+    }
+
     pub fn iter() -> [PluginStatus; 3] {
         [
             PluginStatus::Enabled,
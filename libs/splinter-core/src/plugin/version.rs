@@ -0,0 +1,354 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+/// A mod version, compared with semver-like rules.
+///
+/// Mods are not strict about their versions (`1.20.1-2.3`, `2.0+build.4`, `v3`),
+/// so anything that is not a number is compared as text.
+#[derive(Clone, Debug)]
+pub struct Version {
+    parts: Vec<VersionPart>,
+    pre_release: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+enum VersionPart {
+    Number(u64),
+    Text(String),
+}
+
+impl Version {
+    pub fn parse(version: &str) -> Version {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        // Build metadata does not take part in the ordering.
+        let version = version.split('+').next().unwrap_or(version);
+        let (core, pre_release) = match version.split_once('-') {
+            Some((core, pre_release)) => (core, Some(pre_release.to_string())),
+            None => (version, None),
+        };
+
+        Version {
+            parts: core
+                .split('.')
+                .map(|part| match part.parse() {
+                    Ok(number) => VersionPart::Number(number),
+                    Err(_) => VersionPart::Text(part.to_string()),
+                })
+                .collect(),
+            pre_release,
+        }
+    }
+
+    fn part(&self, index: usize) -> VersionPart {
+        self.parts
+            .get(index)
+            .cloned()
+            .unwrap_or(VersionPart::Number(0))
+    }
+
+    /// Increments the part at the index and drops everything after it, `1.2.3` bumped at 1 becomes `1.3`.
+    fn bump(&self, index: usize) -> Version {
+        let mut parts: Vec<VersionPart> = (0..=index).map(|i| self.part(i)).collect();
+        if let VersionPart::Number(number) = &mut parts[index] {
+            *number += 1;
+        }
+
+        Version {
+            parts,
+            pre_release: None,
+        }
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for i in 0..self.parts.len().max(other.parts.len()) {
+            let ordering = match (self.part(i), other.part(i)) {
+                (VersionPart::Number(v0), VersionPart::Number(v1)) => v0.cmp(&v1),
+                // Numbers are considered newer than text like "beta"
+                (VersionPart::Number(_), VersionPart::Text(_)) => Ordering::Greater,
+                (VersionPart::Text(_), VersionPart::Number(_)) => Ordering::Less,
+                (VersionPart::Text(v0), VersionPart::Text(v1)) => v0.cmp(&v1),
+            };
+
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+
+        // A pre-release comes before the release itself.
+        match (&self.pre_release, &other.pre_release) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(v0), Some(v1)) => Version::parse(v0).cmp(&Version::parse(v1)),
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A version requirement in the Fabric predicate syntax, or the Maven range syntax used by Forge.
+///
+/// The requirement is met if any of the alternatives is met,
+/// an alternative is met if all of its predicates are met.
+#[derive(Clone, Debug)]
+pub struct VersionRange {
+    source: String,
+    alternatives: Vec<Vec<Predicate>>,
+}
+
+#[derive(Clone, Debug)]
+enum Predicate {
+    Equal(Version),
+    Greater(Version),
+    GreaterEqual(Version),
+    Less(Version),
+    LessEqual(Version),
+}
+
+impl VersionRange {
+    /// A range which every version satisfies.
+    pub fn any() -> VersionRange {
+        VersionRange {
+            source: "*".to_string(),
+            alternatives: vec![vec![]],
+        }
+    }
+
+    /// Parses a list of alternatives like `[">=1.2 <2", "~3.1"]`.
+    pub fn parse_any<S: AsRef<str>>(alternatives: &[S]) -> VersionRange {
+        if alternatives.is_empty() {
+            return VersionRange::any();
+        }
+
+        VersionRange {
+            source: alternatives
+                .iter()
+                .map(|v| v.as_ref())
+                .collect::<Vec<_>>()
+                .join(" || "),
+            alternatives: alternatives
+                .iter()
+                .map(|alternative| {
+                    Self::split_predicates(alternative.as_ref())
+                        .iter()
+                        .flat_map(|predicate| Self::parse_predicate(predicate))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    pub fn parse(range: &str) -> VersionRange {
+        Self::parse_any(&[range])
+    }
+
+    /// Parses a Maven version range like `[1.2,2.0)` or `[1,2),[3,)`, as used in Forge `mods.toml` files.
+    /// A bare version is only a recommendation in Maven, so it accepts any version.
+    pub fn parse_maven(range: &str) -> VersionRange {
+        let mut alternatives = Vec::new();
+        let mut rest = range.trim();
+        while let Some(start) = rest.find(['[', '(']) {
+            let Some(end) = rest[start..].find([']', ')']).map(|end| start + end) else {
+                break;
+            };
+
+            let inclusive_min = rest[start..].starts_with('[');
+            let inclusive_max = rest[end..].starts_with(']');
+            let inner = &rest[start + 1..end];
+
+            let mut predicates = Vec::new();
+            match inner.split_once(',') {
+                Some((min, max)) => {
+                    let (min, max) = (min.trim(), max.trim());
+                    if !min.is_empty() {
+                        let min = Version::parse(min);
+                        predicates.push(match inclusive_min {
+                            true => Predicate::GreaterEqual(min),
+                            false => Predicate::Greater(min),
+                        });
+                    }
+                    if !max.is_empty() {
+                        let max = Version::parse(max);
+                        predicates.push(match inclusive_max {
+                            true => Predicate::LessEqual(max),
+                            false => Predicate::Less(max),
+                        });
+                    }
+                }
+                None => predicates.push(Predicate::Equal(Version::parse(inner))),
+            }
+
+            alternatives.push(predicates);
+            rest = &rest[end + 1..];
+        }
+
+        if alternatives.is_empty() {
+            return VersionRange::any();
+        }
+
+        VersionRange {
+            source: range.trim().to_string(),
+            alternatives,
+        }
+    }
+
+    pub fn is_any(&self) -> bool {
+        self.alternatives
+            .iter()
+            .any(|predicates| predicates.is_empty())
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        let version = Version::parse(version);
+        self.alternatives.iter().any(|predicates| {
+            predicates.iter().all(|predicate| match predicate {
+                Predicate::Equal(v) => &version == v,
+                Predicate::Greater(v) => &version > v,
+                Predicate::GreaterEqual(v) => &version >= v,
+                Predicate::Less(v) => &version < v,
+                Predicate::LessEqual(v) => &version <= v,
+            })
+        })
+    }
+
+    /// Splits the predicates of an alternative at whitespace.
+    /// An operator standing on its own belongs to the version after it, like in `>= 1.0`.
+    fn split_predicates(alternative: &str) -> Vec<String> {
+        let mut predicates = Vec::new();
+        let mut operator = String::new();
+        for token in alternative.split_whitespace() {
+            operator.push_str(token);
+            if !token.chars().all(|c| "<>=~^".contains(c)) {
+                predicates.push(std::mem::take(&mut operator));
+            }
+        }
+        predicates
+    }
+
+    fn parse_predicate(predicate: &str) -> Vec<Predicate> {
+        let (operator, version) = match predicate.find(|c: char| !"<>=~^".contains(c)) {
+            Some(index) => predicate.split_at(index),
+            None => return vec![],
+        };
+
+        // x-ranges like 1.2.x only fix the parts before the wildcard.
+        let wildcard = version
+            .split('.')
+            .position(|part| matches!(part, "x" | "X" | "*"));
+        if let Some(index) = wildcard {
+            if index == 0 {
+                return vec![];
+            }
+
+            let fixed = version.split('.').take(index).collect::<Vec<_>>().join(".");
+            let min = Version::parse(&fixed);
+            let max = min.bump(index - 1);
+            return vec![Predicate::GreaterEqual(min), Predicate::Less(max)];
+        }
+
+        let version = Version::parse(version);
+        match operator {
+            ">=" => vec![Predicate::GreaterEqual(version)],
+            "<=" => vec![Predicate::LessEqual(version)],
+            ">" => vec![Predicate::Greater(version)],
+            "<" => vec![Predicate::Less(version)],
+            // Same major and minor version.
+            "~" => {
+                let max = version.bump(1.min(version.parts.len() - 1));
+                vec![Predicate::GreaterEqual(version), Predicate::Less(max)]
+            }
+            // Same major version.
+            "^" => {
+                let max = version.bump(0);
+                vec![Predicate::GreaterEqual(version), Predicate::Less(max)]
+            }
+            _ => vec![Predicate::Equal(version)],
+        }
+    }
+}
+
+// Ranges are compared by how they are written, which is enough to remove duplicate declarations.
+impl PartialEq for VersionRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Eq for VersionRange {}
+
+impl Hash for VersionRange {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.source.hash(state);
+    }
+}
+
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plugin::version::{Version, VersionRange};
+
+    #[test]
+    fn ordering() {
+        assert!(Version::parse("1.2.10") > Version::parse("1.2.9"));
+        assert!(Version::parse("1.2") == Version::parse("1.2.0"));
+        assert!(Version::parse("1.0.0-beta.2") < Version::parse("1.0.0"));
+        assert!(Version::parse("1.0.0-beta.2") > Version::parse("1.0.0-beta.1"));
+        assert!(Version::parse("0.15.11+build.3") == Version::parse("0.15.11"));
+    }
+
+    #[test]
+    fn ranges() {
+        assert!(VersionRange::parse("*").matches("0.1"));
+        assert!(VersionRange::parse(">=0.15.0").matches("0.15.11"));
+        assert!(!VersionRange::parse(">=0.15.0").matches("0.14.21"));
+        assert!(VersionRange::parse("~1.20").matches("1.20.4"));
+        assert!(!VersionRange::parse("~1.20").matches("1.21"));
+        assert!(VersionRange::parse("^5.1").matches("5.9.0"));
+        assert!(!VersionRange::parse("^5.1").matches("6.0.0"));
+        assert!(VersionRange::parse("1.20.x").matches("1.20.1"));
+        assert!(!VersionRange::parse("1.20.x").matches("1.19.4"));
+        assert!(VersionRange::parse(">=1.19 <1.20").matches("1.19.4"));
+        assert!(!VersionRange::parse(">=1.19 <1.20").matches("1.20"));
+        let spaced = VersionRange::parse(">= 1.0 <2");
+        assert!(spaced.matches("1.5"));
+        assert!(!spaced.matches("0.9"));
+        assert!(!spaced.matches("2.0"));
+        assert!(VersionRange::parse("1.2.3").matches("1.2.3"));
+        assert!(!VersionRange::parse("1.2.3").matches("1.2.4"));
+
+        let maven = VersionRange::parse_maven("[1.2,2.0)");
+        assert!(maven.matches("1.2"));
+        assert!(maven.matches("1.9.9"));
+        assert!(!maven.matches("2.0"));
+        assert!(VersionRange::parse_maven("(,1.0]").matches("0.5"));
+        assert!(!VersionRange::parse_maven("[1,2),[3,)").matches("2.5"));
+        assert!(VersionRange::parse_maven("[1,2),[3,)").matches("3.1"));
+        assert!(VersionRange::parse_maven("1.0").is_any());
+
+        let alternatives = VersionRange::parse_any(&["1.19.2", "~1.20"]);
+        assert!(alternatives.matches("1.19.2"));
+        assert!(alternatives.matches("1.20.1"));
+        assert!(!alternatives.matches("1.19.3"));
+    }
+}
@@ -0,0 +1,87 @@
+//! Fixtures shared by the tests: jars built in memory, and instances written to a directory of their own.
+
+use std::fs::{create_dir_all, remove_dir_all, write};
+use std::io::{Cursor, Write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use zip::write::SimpleFileOptions;
+use zip::ZipWriter;
+
+use crate::modpack::Modpack;
+
+/// An empty directory which gets removed again once the test is done with it.
+///
+/// Every directory is unique, so tests running in parallel and runs of the tests at the same time never share one.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("splinter-{name}-{}-{count}", std::process::id()));
+        remove_dir_all(&path).ok();
+        create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        remove_dir_all(&self.0).ok();
+    }
+}
+
+/// Writes the file, along with the directories it is in.
+pub fn write_file(path: &Path, data: impl AsRef<[u8]>) {
+    create_dir_all(path.parent().unwrap()).unwrap();
+    write(path, data).unwrap();
+}
+
+/// Builds an in-memory jar out of the given files.
+pub fn jar(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in files {
+        writer
+            .start_file(*name, SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(data).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+/// A Fabric mod with the fields added to its `fabric.mod.json`, along with the jars nested in it.
+pub fn fabric_jar(id: &str, fields: &str, jars: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let json = format!(r#"{{"id": "{id}", "version": "1.0.0", "name": "{id}"{fields}}}"#);
+    let mut files = vec![("fabric.mod.json", json.as_bytes())];
+    files.extend(jars.iter().map(|(name, data)| (*name, data.as_slice())));
+    jar(&files)
+}
+
+/// Writes an instance with a Fabric mod for every id.
+pub fn instance(name: &str, ids: &[&str]) -> TempDir {
+    let game_dir = TempDir::new(name);
+    create_dir_all(game_dir.join("mods")).unwrap();
+    for id in ids {
+        write_file(&game_dir.join("mods").join(format!("{id}.jar")), fabric_jar(id, "", &[]));
+    }
+    game_dir
+}
+
+/// Loads the instance the way the app does, until every mod is read.
+pub fn load(game_dir: &Path) -> Modpack {
+    let mut modpack = Modpack::new(game_dir.to_path_buf()).unwrap();
+    while modpack.is_loading() {
+        modpack.tick_loader();
+    }
+    modpack
+}
@@ -0,0 +1,378 @@
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread::sleep;
+use std::time::Duration;
+
+use regex::Regex;
+use serde_json::{Value, json};
+use splinter_core::{Modpack, Oracle, PluginStatus, Severity};
+
+const USAGE: &str = "Usage: splinter cli [--instance <path>] <command>
+
+Commands:
+  open <instance>         Opens the instance, the other commands use it from then on
+  status                  Prints the mods and how far the search is, without changing them
+  split                   The issue showed up with the enabled mods
+  invert                  The issue did not show up with the enabled mods
+  undo                    Goes back one step
+  redo                    Goes forward one step
+  force <id> on|off|clear Forces a mod on or off, or lets the search decide again
+  restore                 Ends the search and renames every jar back
+  report                  Prints the mods which could still be faulty, without changing them
+  auto [options] -- <cmd> Answers every test by running the command in the instance,
+                          anything but exit code 0 means the issue showed up
+
+Options of auto:
+  --timeout <seconds>     Stops the command after this long
+  --timeout-is broken|fixed
+                          What running into the timeout means, fixed by default
+  --broken-if <regex>     A line of output which means the issue showed up
+  --fixed-if <regex>      A line of output which means the issue did not show up
+  --max-steps <n>         Gives up after this many tests, 100 by default";
+
+const DEFAULT_MAX_STEPS: usize = 100;
+
+/// Runs the search without a window, every command prints JSON to stdout.
+/// The arguments are the ones after `splinter cli`.
+///
+/// Every command loads the instance again, the search continues from the session splinter keeps in it.
+pub fn run(args: Vec<String>) -> ExitCode {
+    let (output, success) = match command(args) {
+        Ok(output) => {
+            let failed = output["notifications"]
+                .as_array()
+                .is_some_and(|v| v.iter().any(|v| v["severity"] == "error"));
+            (output, !failed)
+        }
+        Err(error) => (json!({ "error": error }), false),
+    };
+
+    println!("{output:#}");
+    if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+enum Command {
+    Open(PathBuf),
+    Status,
+    Split,
+    Invert,
+    Undo,
+    Redo,
+    Force(String, Option<bool>),
+    Restore,
+    Report,
+    Auto(Oracle, usize),
+}
+
+impl Command {
+    /// If the command only looks at the instance, the mods keep the names they have on disk.
+    fn is_read_only(&self) -> bool {
+        matches!(self, Command::Open(_) | Command::Status | Command::Report)
+    }
+
+    fn parse(args: &[&str]) -> Result<Command, String> {
+        Ok(match args {
+            ["open", path] => Command::Open(PathBuf::from(path)),
+            ["status"] => Command::Status,
+            ["split"] => Command::Split,
+            ["invert"] => Command::Invert,
+            ["undo"] => Command::Undo,
+            ["redo"] => Command::Redo,
+            ["force", id, forced] => Command::Force(
+                id.to_string(),
+                match *forced {
+                    "on" => Some(true),
+                    "off" => Some(false),
+                    "clear" => None,
+                    _ => return Err(format!("Expected on, off or clear, got \"{forced}\"")),
+                },
+            ),
+            ["restore"] => Command::Restore,
+            ["report"] => Command::Report,
+            ["auto", options @ ..] => Self::parse_auto(options)?,
+            _ => return Err(USAGE.to_string()),
+        })
+    }
+
+    fn parse_auto(mut args: &[&str]) -> Result<Command, String> {
+        let mut oracle = Oracle::new(vec![]);
+        let mut max_steps = DEFAULT_MAX_STEPS;
+        loop {
+            match args {
+                ["--", command @ ..] => {
+                    oracle.command = command.iter().map(|arg| arg.to_string()).collect();
+                    break;
+                }
+                ["--timeout", seconds, rest @ ..] => {
+                    let seconds: f64 = seconds
+                        .parse()
+                        .map_err(|_| format!("Expected seconds, got \"{seconds}\""))?;
+                    oracle.timeout = Some(
+                        Duration::try_from_secs_f64(seconds)
+                            .map_err(|_| format!("Expected seconds, got \"{seconds}\""))?,
+                    );
+                    args = rest;
+                }
+                ["--timeout-is", verdict, rest @ ..] => {
+                    oracle.timeout_broken = match *verdict {
+                        "broken" => true,
+                        "fixed" => false,
+                        _ => return Err(format!("Expected broken or fixed, got \"{verdict}\"")),
+                    };
+                    args = rest;
+                }
+                ["--broken-if", pattern, rest @ ..] => {
+                    oracle.broken_pattern = Some(parse_pattern(pattern)?);
+                    args = rest;
+                }
+                ["--fixed-if", pattern, rest @ ..] => {
+                    oracle.fixed_pattern = Some(parse_pattern(pattern)?);
+                    args = rest;
+                }
+                ["--max-steps", steps, rest @ ..] => {
+                    max_steps = steps
+                        .parse()
+                        .map_err(|_| format!("Expected a number of steps, got \"{steps}\""))?;
+                    args = rest;
+                }
+                _ => return Err(USAGE.to_string()),
+            }
+        }
+
+        if oracle.command.is_empty() {
+            return Err("Missing the command to run after --".to_string());
+        }
+        Ok(Command::Auto(oracle, max_steps))
+    }
+}
+
+fn parse_pattern(pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|error| format!("Invalid pattern \"{pattern}\": {error}"))
+}
+
+fn command(mut args: Vec<String>) -> Result<Value, String> {
+    let instance = take_instance(&mut args)?;
+    let command = Command::parse(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+    let instance = match &command {
+        Command::Open(path) => {
+            save_instance(path)?;
+            path.clone()
+        }
+        _ => instance.or_else(saved_instance).ok_or(
+            "No instance is open, open one with `splinter cli open <instance>` or pass --instance <path>",
+        )?,
+    };
+
+    let modpack = match command.is_read_only() {
+        true => Modpack::inspect(instance.clone()),
+        false => Modpack::new(instance.clone()),
+    };
+    let mut modpack = modpack.ok_or(format!("Could not find the mods folder of {instance:?}"))?;
+    if let Command::Restore = command {
+        modpack.restore();
+        return Ok(json!({ "instance": modpack.path(), "restored": true }));
+    }
+
+    while modpack.is_loading() {
+        modpack.tick_loader();
+        sleep(Duration::from_millis(10));
+    }
+
+    match command {
+        Command::Open(_) | Command::Status | Command::Restore => {}
+        Command::Split => modpack.split(),
+        Command::Invert => modpack.invert(),
+        Command::Undo => modpack.undo(),
+        Command::Redo => modpack.redo(),
+        Command::Force(id, forced) => {
+            if !modpack.set_forced(&id, forced) {
+                return Err(format!("There is no mod \"{id}\""));
+            }
+        }
+        Command::Report => return Ok(report(&mut modpack)),
+        Command::Auto(oracle, max_steps) => {
+            let runs: Vec<Value> = modpack
+                .auto_bisect(&oracle, max_steps)
+                .map_err(|error| format!("Could not run {:?}: {error}", oracle.command.join(" ")))?
+                .into_iter()
+                .map(|run| {
+                    json!({
+                        "step": run.step,
+                        "broken": run.broken,
+                        "reason": run.reason,
+                        "transcript": run.transcript,
+                    })
+                })
+                .collect();
+
+            let mut report = report(&mut modpack);
+            report["runs"] = json!(runs);
+            return Ok(report);
+        }
+    }
+
+    Ok(status(&mut modpack))
+}
+
+/// Removes `--instance <path>` from the arguments, the ones after `--` belong to the command `auto` runs.
+fn take_instance(args: &mut Vec<String>) -> Result<Option<PathBuf>, String> {
+    let options = args.iter().position(|arg| arg == "--").unwrap_or(args.len());
+    let Some(i) = args[..options].iter().position(|arg| arg == "--instance") else {
+        return Ok(None);
+    };
+
+    args.remove(i);
+    if i + 1 >= options {
+        return Err("Missing path after --instance".to_string());
+    }
+    Ok(Some(PathBuf::from(args.remove(i))))
+}
+
+fn status(modpack: &mut Modpack) -> Value {
+    let plugins: Vec<Value> = modpack
+        .plugins()
+        .iter()
+        .iter()
+        .map(|plugin| {
+            json!({
+                "id": plugin.metadata.id,
+                "name": plugin.metadata.name,
+                "version": plugin.metadata.version,
+                "file": plugin.path(),
+                "status": plugin.status,
+                "forced": plugin.forced_status,
+                "enabled": plugin.enabled(),
+                "stability": plugin.stability(),
+            })
+        })
+        .collect();
+
+    let mut status = search(modpack);
+    status["plugins"] = json!(plugins);
+    status
+}
+
+/// The mods which could still be faulty, and the ones the search found once it is done.
+fn report(modpack: &mut Modpack) -> Value {
+    let suspects: Vec<&str> = modpack
+        .plugins()
+        .iter()
+        .iter()
+        .filter(|plugin| {
+            plugin.forced_status.is_none() && plugin.status != PluginStatus::NotTheProblem
+        })
+        .map(|plugin| plugin.metadata.id.as_str())
+        .collect();
+    let suspects = json!(suspects);
+
+    let mut report = search(modpack);
+    report["suspects"] = suspects;
+    // A single mod for most searches, ddmin finds the set of mods which only cause the issue together.
+    report["culprits"] = json!(modpack.result());
+    report
+}
+
+/// How far the search is, with the notifications it raised.
+fn search(modpack: &mut Modpack) -> Value {
+    let notifications: Vec<Value> = modpack
+        .take_notifications()
+        .into_iter()
+        .map(|notification| {
+            json!({
+                "severity": match notification.ty {
+                    Severity::Info => "info",
+                    Severity::Warning => "warning",
+                    Severity::Error => "error",
+                },
+                "title": notification.title,
+                "description": notification.description,
+            })
+        })
+        .collect();
+
+    json!({
+        "instance": modpack.path(),
+        "title": modpack.title(),
+        "mode": modpack.mode(),
+        "step": modpack.step(),
+        "steps": modpack.steps(),
+        "estimate": modpack.estimate().map(|estimate| json!({
+            "expected": estimate.expected,
+            "worst": estimate.worst,
+        })),
+        "progress": modpack.progress(),
+        "notifications": notifications,
+    })
+}
+
+/// Where the instance opened last is kept, for the commands after `open`.
+fn state_path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("splinter/cli.json"))
+}
+
+fn save_instance(instance: &Path) -> Result<(), String> {
+    let path = state_path().ok_or("Could not find the data directory")?;
+    let instance = instance
+        .canonicalize()
+        .map_err(|error| format!("Could not open {instance:?}: {error}"))?;
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|error| error.to_string())?;
+    }
+    write(path, json!({ "instance": instance }).to_string()).map_err(|error| error.to_string())
+}
+
+fn saved_instance() -> Option<PathBuf> {
+    let state: Value = serde_json::from_str(&read_to_string(state_path()?).ok()?).ok()?;
+    Some(PathBuf::from(state["instance"].as_str()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::cli::{Command, take_instance};
+
+    #[test]
+    fn parse() {
+        assert!(matches!(Command::parse(&["split"]), Ok(Command::Split)));
+        assert!(matches!(
+            Command::parse(&["force", "sodium", "off"]),
+            Ok(Command::Force(id, Some(false))) if id == "sodium"
+        ));
+        assert!(Command::parse(&["force", "sodium", "maybe"]).is_err());
+        assert!(Command::parse(&["split", "now"]).is_err());
+        assert!(Command::parse(&["status"]).is_ok_and(|command| command.is_read_only()));
+        assert!(Command::parse(&["split"]).is_ok_and(|command| !command.is_read_only()));
+
+        let Ok(Command::Auto(oracle, 100)) = Command::parse(&[
+            "auto", "--timeout", "60", "--timeout-is", "broken", "--fixed-if", "Done", "--", "./start.sh", "nogui",
+        ]) else {
+            panic!("auto did not parse");
+        };
+        assert_eq!(oracle.command, ["./start.sh", "nogui"]);
+        assert_eq!(oracle.timeout, Some(Duration::from_secs(60)));
+        assert!(oracle.timeout_broken);
+        assert!(oracle.fixed_pattern.is_some_and(|pattern| pattern.is_match("Done")));
+        assert!(Command::parse(&["auto", "--"]).is_err());
+        assert!(Command::parse(&["auto", "--broken-if", "(", "--", "true"]).is_err());
+    }
+
+    #[test]
+    fn instance() {
+        let args = |args: &[&str]| args.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let mut split = args(&["--instance", "pack", "split"]);
+        assert_eq!(take_instance(&mut split), Ok(Some("pack".into())));
+        assert_eq!(split, ["split"]);
+
+        // The command run by auto keeps its own --instance.
+        let mut auto = args(&["auto", "--", "./run.sh", "--instance", "server"]);
+        assert_eq!(take_instance(&mut auto), Ok(None));
+        assert_eq!(auto.len(), 5);
+        assert!(take_instance(&mut args(&["split", "--instance", "--", "true"])).is_err());
+    }
+}
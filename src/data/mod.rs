@@ -1,6 +0,0 @@
-mod modpack;
-mod plugin;
-
-
-pub use modpack::*;
-pub use plugin::*;
\ No newline at end of file
@@ -1,21 +1,24 @@
 use std::path::PathBuf;
+use std::process::ExitCode;
 
-use eframe::egui::{Color32, Context, Frame, Id, Margin, Rounding, Style, Vec2, ViewportBuilder, Visuals};
+use eframe::egui::{Color32, Context, Frame, Id, Margin, Rounding, Style, TextureHandle, Vec2, ViewportBuilder, Visuals};
 use eframe::{egui, App, NativeOptions};
 use tracing::level_filters::LevelFilter;
+use tracing::warn;
 
 use splinter_animation::config::AnimationConfig;
 use splinter_animation::{AnimationManager};
+use splinter_core::Estimate;
 use splinter_event::{EventSystem, EventTracker};
 
-use crate::data::PluginStatus;
 use crate::ui::{animation, color, load_fonts};
+use crate::ui::notification::Notifications;
 use crate::view::home::HomeView;
 use crate::view::search::SearchView;
 use crate::view::Header;
 use crate::ApplicationView::{Home, Search};
 
-mod data;
+mod cli;
 mod ui;
 pub mod view;
 
@@ -34,13 +37,23 @@ pub enum PackOperationEvent {
     Invert,
 }
 
-fn main() {
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
     let subscriber = tracing_subscriber::fmt()
         .compact()
         .with_max_level(LevelFilter::DEBUG)
+        // The commands print JSON to stdout.
+        .with_writer(std::io::stderr)
         .finish();
     tracing::subscriber::set_global_default(subscriber).unwrap();
 
+    // Launchers and file managers may pass arguments of their own, only `splinter cli` runs without a window.
+    match args.split_first() {
+        Some((command, args)) if command == "cli" => return cli::run(args.to_vec()),
+        Some(_) => warn!("Ignoring the arguments {args:?}, use `splinter cli` for the command line"),
+        None => {}
+    }
+
     eframe::run_native(
         "Splinter",
         NativeOptions {
@@ -76,7 +89,8 @@ fn main() {
 
             Box::new(Application {
                 header: Header::new(),
-                view: Home(HomeView::new()),
+                notifications: Notifications::new(),
+                view: Home(HomeView::new(ctx)),
                 state: ApplicationState {
                     modpack_status: ModpackStatus::Empty,
                     events: EventSystem::new(),
@@ -86,6 +100,7 @@ fn main() {
         }),
     )
     .unwrap();
+    ExitCode::SUCCESS
 }
 
 pub struct ApplicationState {
@@ -97,9 +112,16 @@ pub enum ModpackStatus {
     Empty,
     Active {
         path: PathBuf,
+        /// The name of the instance, with what it runs on.
+        title: String,
+        icon: Option<TextureHandle>,
         is_loaded: bool,
         can_undo: bool,
         can_redo: bool,
+        /// The step of the undo queue the user is at, counting from 1.
+        step: usize,
+        steps: usize,
+        estimate: Option<Estimate>,
     },
 }
 
@@ -107,15 +129,18 @@ pub enum ModpackStatus {
 pub enum ModpackEvent {
     Load(PathBuf),
     Exit,
+    /// Exits and renames every jar back to how it was before the session.
+    Restore,
 }
 
 pub enum ApplicationView {
-    Search(SearchView),
+    Search(Box<SearchView>),
     Home(HomeView),
 }
 
 pub struct Application {
     header: Header,
+    notifications: Notifications,
     view: ApplicationView,
     state: ApplicationState,
 
@@ -129,24 +154,20 @@ impl App for Application {
             match event {
                 ModpackEvent::Load(path) => {
                     if let Some(value) = SearchView::new(path.clone(), ctx) {
-                        self.view = Search(value);
+                        self.view = Search(Box::new(value));
                     }
                 }
-                ModpackEvent::Exit => {
+                ModpackEvent::Exit | ModpackEvent::Restore => {
                     // We want to re-enable the plugins
                     match &mut self.view {
-                        Search(search) => {
-                            for plugin in search.modpack.plugins_mut().iter_mut() {
-                                if plugin.forced_status.is_none() {
-                                    plugin.status = PluginStatus::Enabled;
-                                    plugin.push_changes();
-                                }
-                            }
+                        Search(search) if matches!(event, ModpackEvent::Restore) => {
+                            search.modpack.restore()
                         }
+                        Search(search) => search.modpack.end_session(),
                         Home(_) => {}
                     }
                     self.state.modpack_status = ModpackStatus::Empty;
-                    self.view = Home(HomeView::new());
+                    self.view = Home(HomeView::new(ctx));
                 }
             }
         }
@@ -165,6 +186,7 @@ impl App for Application {
                     Search(view) => view.ui(&mut self.state, ui),
                     Home(view) => view.ui(&mut self.state, ui),
                 }
+                self.notifications.ui(&mut self.state, ui);
                 animation(ui).end_tick(ui.ctx());
             });
     }
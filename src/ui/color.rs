@@ -11,5 +11,6 @@ pub const BACKGROUND: Color32 = Color32::from_rgb(0x0a, 0x0a ,0x0a);
 
 pub const RED: Color32 = Color32::from_rgb(0xff, 0x72, 0x74);
 pub const GREEN: Color32 = Color32::from_rgb(0xa6, 0xff, 0x89);
+pub const YELLOW: Color32 = Color32::from_rgb(0xff, 0xd8, 0x7a);
 pub const SKY: Color32 = Color32::from_rgb(0x99, 0xd1, 0xdb);
 pub const BLUE: Color32 = Color32::from_rgb(0x8c, 0xaa, 0xee);
@@ -1,33 +1,48 @@
 use std::sync::Arc;
 
 use eframe::egui::{
-	FontData, FontDefinitions, FontFamily, FontTweak, Id, Ui,
+	Color32, ColorImage, Context, FontData, FontDefinitions, FontFamily, FontTweak, Id, TextureHandle, TextureOptions, Ui,
 };
+use tracing::debug;
 
 use splinter_animation::AnimationManager;
+pub use splinter_core::{NotificationEvent, Severity};
+use splinter_core::{IconImage, ModpackMetadata};
 
 pub mod color;
 pub mod icon;
+pub mod notification;
 pub mod progress;
 
-#[derive(Debug)]
-pub struct NotificationEvent {
-    pub title: String,
-    pub description: String,
-    pub ty: Severity,
+pub fn animation(ui: &mut Ui) -> AnimationManager {
+    ui.data_mut(|d| d.get_persisted::<AnimationManager>(Id::NULL))
+        .unwrap()
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
-pub enum Severity {
-    Info,
-    Warning,
-    Error,
+/// Uploads an icon the core decoded.
+pub fn load_texture(ctx: &Context, name: impl Into<String>, icon: &IconImage, options: TextureOptions) -> TextureHandle {
+    ctx.load_texture(
+        name,
+        ColorImage {
+            size: icon.size,
+            pixels: icon
+                .rgba
+                .chunks_exact(4)
+                .map(|v| Color32::from_rgba_premultiplied(v[0], v[1], v[2], v[3]))
+                .collect(),
+        },
+        options,
+    )
 }
 
-pub fn animation(ui: &mut Ui) -> AnimationManager {
-    ui.data_mut(|d| d.get_persisted::<AnimationManager>(Id::NULL))
-        .unwrap()
+/// Loads the icon the launcher shows for the instance.
+pub fn instance_icon(metadata: &ModpackMetadata, ctx: &Context) -> Option<TextureHandle> {
+    let path = metadata.icon.as_ref()?;
+    let Some(icon) = IconImage::open(path, 64) else {
+        debug!("Could not load the instance icon {path:?}");
+        return None;
+    };
+    Some(load_texture(ctx, path.to_string_lossy(), &icon, TextureOptions::LINEAR))
 }
 
 #[derive(Copy, Clone, Debug)]
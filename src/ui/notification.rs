@@ -0,0 +1,310 @@
+use std::collections::VecDeque;
+
+use eframe::egui::{
+    Align, Align2, Area, Color32, Frame, Id, Label, Layout, Margin, Order, Response, RichText,
+    ScrollArea, Sense, Ui, Vec2, Widget,
+};
+use splinter_event::EventTracker;
+use splinter_icon::icon;
+
+use crate::ApplicationState;
+use crate::ui::icon::{Icon, draw_icon};
+use crate::ui::{NotificationEvent, Severity, animation, color};
+
+const WIDTH: f32 = 360.0;
+/// The most toasts shown at once, older ones get dismissed early.
+const MAX_TOASTS: usize = 4;
+const MAX_HISTORY: usize = 100;
+
+/// Shows every [`NotificationEvent`] as a toast in the bottom right corner,
+/// and keeps them in a history which can be opened with the bell below the toasts.
+pub struct Notifications {
+    tracker: EventTracker,
+    next_id: u64,
+    toasts: Vec<Toast>,
+    history: VecDeque<Notification>,
+    show_history: bool,
+    unread: usize,
+}
+
+#[derive(Clone)]
+struct Notification {
+    id: u64,
+    title: String,
+    description: String,
+    ty: Severity,
+}
+
+struct Toast {
+    notification: Notification,
+    /// Seconds until the toast gets dismissed, which only runs out while it is not hovered.
+    remaining: f32,
+    closing: bool,
+}
+
+impl Notifications {
+    pub fn new() -> Notifications {
+        Notifications {
+            tracker: EventTracker::new(),
+            next_id: 0,
+            toasts: vec![],
+            history: VecDeque::new(),
+            show_history: false,
+            unread: 0,
+        }
+    }
+
+    pub fn ui(&mut self, state: &mut ApplicationState, ui: &mut Ui) {
+        let commander = self.tracker.tick(&mut state.events);
+        for event in commander.consume::<NotificationEvent>() {
+            self.push(event);
+        }
+
+        let dt = ui.input(|i| i.stable_dt).min(0.1);
+        Area::new(Id::new("notifications"))
+            .order(Order::Foreground)
+            .anchor(Align2::RIGHT_BOTTOM, Vec2::new(-16.0, -16.0))
+            .show(ui.ctx(), |ui| {
+                ui.set_width(WIDTH);
+                ui.with_layout(Layout::bottom_up(Align::Max), |ui| {
+                    self.bell_ui(ui);
+                    if self.show_history {
+                        ui.add_space(8.0);
+                        self.history_ui(ui);
+                    } else {
+                        self.toasts_ui(ui, dt);
+                    }
+                });
+            });
+
+        if !self.toasts.is_empty() {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    fn push(&mut self, event: &NotificationEvent) {
+        let notification = Notification {
+            id: self.next_id,
+            title: event.title.clone(),
+            description: event.description.clone(),
+            ty: event.ty,
+        };
+        self.next_id += 1;
+
+        self.history.push_front(notification.clone());
+        self.history.truncate(MAX_HISTORY);
+        if !self.show_history {
+            self.unread += 1;
+        }
+
+        self.toasts.push(Toast {
+            remaining: notification.ty.duration(),
+            notification,
+            closing: false,
+        });
+        let open = self.toasts.iter().filter(|toast| !toast.closing).count();
+        for toast in self.toasts.iter_mut().take(open.saturating_sub(MAX_TOASTS)) {
+            toast.closing = true;
+        }
+    }
+
+    fn bell_ui(&mut self, ui: &mut Ui) {
+        if self.history.is_empty() {
+            return;
+        }
+
+        let (rect, response) = ui.allocate_exact_size(Vec2::splat(36.0), Sense::click());
+        let bg = if response.hovered() || self.show_history {
+            color::PANEL
+        } else {
+            color::MANTLE
+        };
+        ui.painter().rect_filled(rect, 18.0, bg);
+        draw_icon(
+            ui.painter(),
+            icon!("notifications"),
+            rect.center(),
+            20.0,
+            color::SUBTEXT1,
+        );
+        if self.unread > 0 {
+            let badge = rect.right_top() + Vec2::new(-6.0, 6.0);
+            ui.painter().circle_filled(badge, 5.0, color::RED);
+        }
+
+        if response.on_hover_text("Notifications").clicked() {
+            self.show_history = !self.show_history;
+            self.unread = 0;
+            if self.show_history {
+                // Everything is in the history, so there is no need to keep the toasts around.
+                self.toasts.clear();
+            }
+        }
+    }
+
+    fn toasts_ui(&mut self, ui: &mut Ui, dt: f32) {
+        let animation = animation(ui);
+        // The newest toast is closest to the bell.
+        for toast in self.toasts.iter_mut().rev() {
+            let id = Id::new("toast").with(toast.notification.id);
+            let visible = animation
+                .get(id)
+                .redirect(if toast.closing { 0.0 } else { 1.0 })
+                .get();
+
+            ui.add_space(8.0 * visible);
+            ui.scope(|ui| {
+                ui.set_opacity(visible);
+                let response = toast
+                    .notification
+                    .ui(ui, id)
+                    .on_hover_text("Right click to dismiss");
+                // Hovering pauses the countdown, it continues where it was once the mouse leaves.
+                if !response.hovered() {
+                    toast.remaining -= dt;
+                }
+
+                if response.secondary_clicked() || toast.remaining <= 0.0 {
+                    toast.closing = true;
+                }
+            });
+        }
+
+        self.toasts.retain(|toast| {
+            let id = Id::new("toast").with(toast.notification.id);
+            !toast.closing || animation.get::<f32>(id).get() > 0.0
+        });
+    }
+
+    fn history_ui(&mut self, ui: &mut Ui) {
+        Frame::none()
+            .fill(color::CRUST)
+            .rounding(8.0)
+            .inner_margin(Margin::same(8.0))
+            .show(ui, |ui| {
+                ui.with_layout(Layout::top_down(Align::Min), |ui| {
+                    ui.set_width(WIDTH - 16.0);
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            RichText::new("Notifications")
+                                .color(color::TEXT)
+                                .strong()
+                                .size(18.0),
+                        );
+                        ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                            if ui
+                                .add(
+                                    Label::new(
+                                        RichText::new("Clear").color(color::BLUE).size(16.0),
+                                    )
+                                    .sense(Sense::click()),
+                                )
+                                .clicked()
+                            {
+                                self.history.clear();
+                                self.show_history = false;
+                            }
+                        });
+                    });
+                    ui.add_space(8.0);
+
+                    ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                        for notification in &self.history {
+                            let id = Id::new("history").with(notification.id);
+                            notification.ui(ui, id);
+                            ui.add_space(6.0);
+                        }
+                    });
+                });
+            });
+    }
+}
+
+impl Notification {
+    /// Draws the notification, clicking it shows the full description.
+    fn ui(&self, ui: &mut Ui, id: Id) -> Response {
+        let expanded = ui.data(|d| d.get_temp::<bool>(id).unwrap_or(false));
+        let fg = self.ty.color();
+
+        let response = Frame::none()
+            .fill(color::MANTLE)
+            .rounding(8.0)
+            .stroke((1.0, fg.gamma_multiply(0.4)))
+            .inner_margin(Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.with_layout(Layout::top_down(Align::Min), |ui| {
+                    ui.set_width(WIDTH - 36.0);
+                    ui.horizontal(|ui| {
+                        Icon::new(self.ty.icon(), 20.0, fg).ui(ui);
+                        ui.add_space(8.0);
+                        ui.label(RichText::new(&self.title).color(fg).strong().size(17.0));
+                    });
+
+                    if !self.description.is_empty() {
+                        ui.add_space(4.0);
+                        let mut description = self.description.as_str();
+                        let mut truncated = false;
+                        if !expanded {
+                            if let Some((first, _)) = description.split_once('\n') {
+                                description = first;
+                                truncated = true;
+                            }
+                        }
+
+                        let label = Label::new(
+                            RichText::new(description).color(color::SUBTEXT1).size(15.0),
+                        );
+                        if expanded {
+                            ui.add(label.wrap(true));
+                        } else {
+                            ui.add(label.truncate(true));
+                        }
+                        if truncated {
+                            ui.label(RichText::new("…").color(color::SUBTEXT0).size(15.0));
+                        }
+                    }
+                });
+            })
+            .response;
+
+        let response = ui.interact(response.rect, id.with("toggle"), Sense::click());
+        if response.clicked() {
+            ui.data_mut(|d| d.insert_temp(id, !expanded));
+        }
+        response
+    }
+}
+
+/// How a notification looks depending on its severity.
+trait SeverityStyle {
+    fn color(&self) -> Color32;
+    fn icon(&self) -> u32;
+    /// How many seconds the toast stays, errors stay longer as they need to be acted on.
+    fn duration(&self) -> f32;
+}
+
+impl SeverityStyle for Severity {
+    fn color(&self) -> Color32 {
+        match self {
+            Severity::Info => color::BLUE,
+            Severity::Warning => color::YELLOW,
+            Severity::Error => color::RED,
+        }
+    }
+
+    fn icon(&self) -> u32 {
+        match self {
+            Severity::Info => icon!("info"),
+            Severity::Warning => icon!("warning"),
+            Severity::Error => icon!("error"),
+        }
+    }
+
+    fn duration(&self) -> f32 {
+        match self {
+            Severity::Info => 5.0,
+            Severity::Warning => 8.0,
+            Severity::Error => 15.0,
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::path::PathBuf;
 
 use dirs::home_dir;
-use eframe::egui::{Align, Align2, Color32, Direction, FontFamily, FontId, Id, Layout, Mesh, Pos2, Rect, Sense, Ui, Vec2, Widget};
+use eframe::egui::load::SizedTexture;
+use eframe::egui::{show_tooltip_text, Align, Align2, Color32, Direction, FontFamily, FontId, Id, Layout, Mesh, Pos2, Rect, Sense, TextureHandle, Ui, Vec2, Widget};
 use eframe::epaint::Vertex;
 use rfd::FileDialog;
 
@@ -10,7 +11,7 @@ use splinter_event::EventTracker;
 use splinter_icon::icon;
 
 use crate::{ApplicationState, ModpackEvent, ModpackStatus};
-use crate::data::ModpackOperationEvent;
+use crate::view::search::modpack::ModpackOperationEvent;
 use crate::ui::{animation, color, ProgressStatus};
 use crate::ui::icon::Icon;
 use crate::ui::progress::{Progress, ProgressSpinner};
@@ -73,6 +74,22 @@ impl Header {
                 );
                 ui.add_space(4.0);
 
+                if let ModpackStatus::Active { is_loaded: true, step, steps, estimate: Some(estimate), .. } = modpack {
+                    let worst = match estimate.worst {
+                        Some(worst) => format!("at most {worst}"),
+                        None => "no limit as answers may be wrong".to_string(),
+                    };
+                    HeaderEntry::label(
+                        ui,
+                        format!("Step {step}/{steps} · ~{:.0} left", estimate.expected.ceil()),
+                        &format!(
+                            "About {:.1} more restarts to find the issue, {worst}.\nThe step is how far you are in the undo history.",
+                            estimate.expected
+                        ),
+                    );
+                    ui.add_space(4.0);
+                }
+
                 HeaderEntry::button(
                     ui,
                     match modpack {
@@ -109,21 +126,32 @@ impl Header {
                 );
                 ui.add_space(4.0);
 
-                let path = match modpack {
-                    ModpackStatus::Empty => None,
-                    ModpackStatus::Active { path, .. } => {
-                        Some(path.clone())
+                HeaderEntry::button(
+                    ui,
+                    is_ready,
+                    icon!("restore"),
+                    color::MANTLE,
+                    color::SUBTEXT1,
+                    "Exits the current session, \nand gives every mod back the file name it had before.",
+                    || {
+                        commander.dispatch(ModpackEvent::Restore);
+                    },
+                );
+                ui.add_space(4.0);
+
+                let (path, title, icon) = match modpack {
+                    ModpackStatus::Empty => (None, "Navigate to...".to_string(), None),
+                    ModpackStatus::Active { path, title, icon, .. } => {
+                        (Some(path.clone()), title.clone(), icon.clone())
                     }
                 };
+                let tooltip = path.as_ref().map(|path| path.to_string_lossy().to_string());
                 HeaderEntry::path(
                     ui,
                     self.progress,
-                    if let Some(path) = &path {
-                        path.to_str().unwrap_or("")
-                    } else {
-                        "Navigate to..."
-                    }
-                        .to_string(),
+                    icon.as_ref(),
+                    title,
+                    tooltip.as_deref(),
                     || {
                         let option = FileDialog::new()
                             .set_directory(
@@ -235,7 +263,9 @@ impl HeaderEntry {
     pub fn path(
         ui: &mut Ui,
         progress: Option<ProgressStatus>,
+        icon: Option<&TextureHandle>,
         text: String,
+        tooltip: Option<&str>,
         func: impl FnOnce(),
     ) {
         let mut desired_size = ui.available_size_before_wrap();
@@ -264,8 +294,13 @@ impl HeaderEntry {
             );
             painter.rect_filled(rect, 8.0, bg);
             ui.add_space(16.0);
-          //  Icon::new(icon, 24.0, color::SUBTEXT1).ui(ui);
-           // ui.add_space(10.0);
+            if let Some(icon) = icon {
+                ui.image(SizedTexture {
+                    id: icon.id(),
+                    size: Vec2::splat(28.0),
+                });
+                ui.add_space(10.0);
+            }
             let painter = ui.painter();
             let galley = painter.layout_no_wrap(
                 text.to_string(),
@@ -355,11 +390,34 @@ impl HeaderEntry {
                 }.ui(ui);
             });
 
+            if let Some(tooltip) = tooltip {
+                if response.hovered() {
+                    show_tooltip_text(ui.ctx(), id.with("header-tooltip"), tooltip);
+                }
+            }
             if response.clicked() {
                 func();
             }
         });
     }
+    pub fn label(ui: &mut Ui, text: String, tooltip: &str) {
+        let galley = ui.painter().layout_no_wrap(
+            text,
+            FontId::new(18.0, FontFamily::Proportional),
+            color::SUBTEXT1,
+        );
+        let desired_size = Vec2::new(galley.size().x + 32.0, HEADER_HEIGHT);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+        response.on_hover_text(tooltip);
+
+        let painter = ui.painter();
+        painter.rect_filled(rect, 8.0, color::MANTLE);
+        let text_pos = Align2::CENTER_CENTER
+            .anchor_rect(Rect::from_center_size(rect.center(), galley.size()))
+            .min;
+        painter.galley(text_pos, galley, color::SUBTEXT1);
+    }
+
     pub fn button(
         ui: &mut Ui,
         enabled: bool,
@@ -1,56 +1,70 @@
-use std::fs::read_dir;
 use std::path::PathBuf;
 
-use eframe::egui::{Align, Align2, Color32, FontId, FontSelection, Label, Layout, RichText, ScrollArea, Sense, Style, Ui, Vec2, Widget};
+use eframe::egui::{Align, Color32, Context, FontSelection, Layout, Pos2, Rect, Response, RichText, ScrollArea, Sense, Style, TextureHandle, Ui, Vec2, Widget};
 use eframe::egui::text::LayoutJob;
+use splinter_core::{discover, Modpack, ModpackMetadata, Platform, Session};
 use splinter_event::EventTracker;
 use splinter_icon::icon;
 
 use crate::{ApplicationState, ModpackEvent};
-use crate::ui::color;
+use crate::ui::{color, instance_icon};
 use crate::ui::icon::Icon;
 
 pub struct HomeView {
-    suggested_instances: Vec<PathBuf>,
+    instances: Vec<InstanceEntry>,
+    // The instances with a session which can be continued, last used first.
+    suggested_instances: Vec<usize>,
     tracker: EventTracker,
 }
 
+/// An instance of a launcher found on this computer.
+struct InstanceEntry {
+    // The game directory, starting with ~ if it is in the home directory.
+    path: PathBuf,
+    launcher: &'static str,
+    title: String,
+    icon: Option<TextureHandle>,
+    session: Option<Session>,
+}
+
 impl HomeView {
-    pub fn new() -> HomeView {
-        let mut suggested_instances = vec![];
-
-        if let Some(path) = dirs::data_dir() {
-            let buf = path.join("multimc/instances");
-            if let Ok(value) = read_dir(buf) {
-                for entry in value.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        if path.join(".minecraft/mods").exists() {
-                            suggested_instances.push(path);
-                        }
+    pub fn new(ctx: &Context) -> HomeView {
+        let home = dirs::home_dir();
+        let instances: Vec<InstanceEntry> = discover()
+            .into_iter()
+            .map(|instance| {
+                let game_dir = instance.mods.parent().unwrap_or(&instance.mods).to_path_buf();
+                let metadata = ModpackMetadata::new(&game_dir);
+                let platform = Platform::new(&game_dir);
+
+                // Clean up paths
+                let mut path = game_dir.clone();
+                if let Some(home) = &home {
+                    if let Ok(new) = game_dir.strip_prefix(home) {
+                        path = PathBuf::from("~").join(new);
                     }
                 }
-            }
-        }
+                InstanceEntry {
+                    path,
+                    launcher: instance.launcher,
+                    title: metadata.title(&platform),
+                    icon: instance_icon(&metadata, ctx),
+                    session: Modpack::session(&game_dir),
+                }
+            })
+            .collect();
 
+        let mut suggested_instances: Vec<usize> = (0..instances.len())
+            .filter(|i| instances[*i].session.is_some())
+            .collect();
         // Sort by last used
-        suggested_instances.sort_by(|v0, v1| {
-            let v0 = v0.metadata().ok().and_then(|v| v.modified().ok());
-            let v1 = v1.metadata().ok().and_then(|v| v.modified().ok());
-            v1.cmp(&v0)
+        suggested_instances.sort_by_cached_key(|i| {
+            let session = Modpack::game_dir(instances[*i].path.clone()).map(|dir| Session::path(&dir));
+            std::cmp::Reverse(session.and_then(|v| v.metadata().ok()?.modified().ok()))
         });
 
-        // Clean up paths
-        let home = dirs::home_dir();
-        if let Some(home) = home {
-            for path in &mut suggested_instances {
-                if let Ok(new) = path.strip_prefix(&home) {
-                    *path = PathBuf::from("~").join(new);
-                }
-            }
-        }
-
         HomeView {
+            instances,
             suggested_instances,
             tracker: EventTracker::new(),
         }
@@ -77,66 +91,100 @@ impl HomeView {
                 );
                 ui.add_space(4.0);
 
-                ui.horizontal(|ui| {
-                    if !self.suggested_instances.is_empty() {
-                        ui.vertical(|ui| {
-                            ui.add_space(32.0);
-                            ui.allocate_ui_with_layout(
-                                Vec2::new(0.0, 32.0),
-                                Layout::left_to_right(Align::Center),
-                                |ui| {
-                                    Icon::new(icon!("history"), 24.0, color::TEXT).ui(ui);
-                                    ui.add_space(6.0);
-                                    ui.label(
-                                        RichText::new("Recover session".to_string())
-                                            .color(color::TEXT)
-                                            .strong()
-                                            .size(24.0),
-                                    );
-                                },
-                            );
-
-                            ui.add_space(8.0);
-                            for path in &self.suggested_instances {
-                                ui.allocate_ui_with_layout(
-                                    Vec2::new(ui.available_rect_before_wrap().width() / 2.0, 24.0),
-                                    Layout::left_to_right(Align::Center),
-                                    |ui| {
-                                        ui.set_min_size(ui.available_size_before_wrap());
-                                        let rect = ui.min_rect();
-                                        let response = ui.interact(
-                                            rect,
-                                            ui.next_auto_id(),
-                                            Sense::click_and_drag(),
-                                        );
-
-                                        if response.clicked() {
-                                            commander.dispatch(ModpackEvent::Load(path.clone()));
-                                        }
-                                        let style = Style::default();
-                                        let mut layout_job = LayoutJob::default();
-                                        let mut text = RichText::new(format!("{path:?}"))
-                                            .color(color::BLUE)
-                                            .strong()
-                                            .size(16.0);
-
-                                        if response.hovered() {
-                                            text = text.color(color::SKY);
-                                            text = text.underline();
-                                        }
-                                        text.append_to(&mut layout_job, &style, FontSelection::Default, Align::Center);
-                                        
-                                        let painter = ui.painter();
-                                        let galley = painter.layout_job(layout_job);
-                                        painter.galley(rect.left_top(), galley, Color32::RED);
-                                    },
-                                );
-                                ui.add_space(6.0);
-                            }
-                        });
+                if !self.suggested_instances.is_empty() {
+                    section_ui(ui, icon!("history"), "Recover session");
+                    for instance in self.suggested_instances.iter().map(|i| &self.instances[*i]) {
+                        let Some(session) = &instance.session else {
+                            continue;
+                        };
+                        let text = format!("{} · step {} of {}", instance.title, session.step(), session.steps());
+                        let response = link_ui(ui, instance.icon.as_ref(), text);
+                        if response.on_hover_text(instance.path.to_string_lossy()).clicked() {
+                            commander.dispatch(ModpackEvent::Load(instance.path.clone()));
+                        }
                     }
-                });
+                }
+
+                if !self.instances.is_empty() {
+                    section_ui(ui, icon!("folder_open"), "Instances");
+                    for instance in &self.instances {
+                        let response = link_ui(ui, instance.icon.as_ref(), instance.title.clone());
+                        let tooltip = format!("{}\n{}", instance.launcher, instance.path.to_string_lossy());
+                        if response.on_hover_text(tooltip).clicked() {
+                            commander.dispatch(ModpackEvent::Load(instance.path.clone()));
+                        }
+                    }
+                }
             });
         });
     }
 }
+
+fn section_ui(ui: &mut Ui, icon: u32, text: &str) {
+    ui.add_space(32.0);
+    ui.allocate_ui_with_layout(
+        Vec2::new(0.0, 32.0),
+        Layout::left_to_right(Align::Center),
+        |ui| {
+            Icon::new(icon, 24.0, color::TEXT).ui(ui);
+            ui.add_space(6.0);
+            ui.label(
+                RichText::new(text)
+                    .color(color::TEXT)
+                    .strong()
+                    .size(24.0),
+            );
+        },
+    );
+    ui.add_space(8.0);
+}
+
+/// Draws a line of text which can be clicked, with the icon in front of it.
+fn link_ui(ui: &mut Ui, icon: Option<&TextureHandle>, text: String) -> Response {
+    let response = ui.allocate_ui_with_layout(
+        Vec2::new(ui.available_rect_before_wrap().width() / 2.0, 24.0),
+        Layout::left_to_right(Align::Center),
+        |ui| {
+            ui.set_min_size(ui.available_size_before_wrap());
+            let rect = ui.min_rect();
+            let response = ui.interact(
+                rect,
+                ui.next_auto_id(),
+                Sense::click_and_drag(),
+            );
+
+            let mut pos = rect.left_top();
+            if let Some(icon) = icon {
+                let size = Vec2::splat(rect.height());
+                ui.painter().image(
+                    icon.id(),
+                    Rect::from_min_size(pos, size),
+                    Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                    Color32::WHITE,
+                );
+                pos.x += size.x + 8.0;
+            }
+
+            let style = Style::default();
+            let mut layout_job = LayoutJob::default();
+            let mut text = RichText::new(text)
+                .color(color::BLUE)
+                .strong()
+                .size(16.0);
+
+            if response.hovered() {
+                text = text.color(color::SKY);
+                text = text.underline();
+            }
+            text.append_to(&mut layout_job, &style, FontSelection::Default, Align::Center);
+
+            let painter = ui.painter();
+            let galley = painter.layout_job(layout_job);
+            let pos = Pos2::new(pos.x, rect.center().y - galley.size().y / 2.0);
+            painter.galley(pos, galley, Color32::RED);
+            response
+        },
+    ).inner;
+    ui.add_space(6.0);
+    response
+}
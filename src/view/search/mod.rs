@@ -2,16 +2,19 @@ use std::path::PathBuf;
 
 use eframe::egui::{Context, Ui};
 
+use modpack::ModpackView;
+
 use crate::ApplicationState;
-use crate::data::Modpack;
+
+pub mod modpack;
 
 pub struct SearchView {
-    pub modpack: Modpack,
+    pub modpack: ModpackView,
 }
 impl SearchView {
     pub fn new(path: PathBuf, ctx: &Context) -> Option<SearchView> {
         Some(SearchView {
-            modpack: Modpack::new(path, ctx)?,
+            modpack: ModpackView::new(path, ctx)?,
         })
     }
     pub fn ui(&mut self, state: &mut ApplicationState, ui: &mut Ui) {
@@ -0,0 +1,126 @@
+use eframe::egui::{Frame, Label, Margin, RichText, ScrollArea, Sense, SidePanel, Ui};
+use splinter_core::Resolution;
+
+use crate::ui::color;
+use crate::view::search::modpack::ModpackView;
+
+/// A disabled mod which enabled mods depend on, the mod loader is going to refuse to start without it.
+pub struct AskingEnable {
+    pub id: String,
+    pub depended_by: Vec<String>,
+    pub kind: AskingKind,
+}
+
+#[derive(Copy, Clone)]
+pub enum AskingKind {
+    /// The mod is forced off, so splitting could not enable it.
+    SplitDependency,
+    /// The user changed a forced status which left the mod disabled.
+    MakingForce,
+}
+
+impl ModpackView {
+    /// Lists the mods we advise the user to enable, with what to do about them.
+    pub(super) fn ask_ui(&mut self, ui: &mut Ui) {
+        if self.to_ask.is_empty() {
+            return;
+        }
+
+        let mut answer = None;
+        SidePanel::right("asking-enable")
+            .resizable(false)
+            .exact_width(320.0)
+            .frame(Frame::none().inner_margin(Margin {
+                left: 8.0,
+                ..Margin::ZERO
+            }))
+            .show_inside(ui, |ui| {
+                ui.label(
+                    RichText::new("Missing dependencies")
+                        .color(color::TEXT)
+                        .strong()
+                        .size(18.0),
+                );
+                ui.add_space(8.0);
+
+                ScrollArea::vertical().show(ui, |ui| {
+                    for ask in &self.to_ask {
+                        let Some(plugin) = self.modpack.plugins().get(&ask.id) else {
+                            continue;
+                        };
+
+                        Frame::none()
+                            .fill(color::MANTLE)
+                            .rounding(8.0)
+                            .inner_margin(Margin::same(8.0))
+                            .show(ui, |ui| {
+                                ui.set_width(ui.available_width());
+                                ui.label(
+                                    RichText::new(&plugin.metadata.name)
+                                        .color(color::YELLOW)
+                                        .size(17.0),
+                                );
+                                ui.label(
+                                    RichText::new(match ask.kind {
+                                        AskingKind::SplitDependency => {
+                                            "Is forced off, so it was kept disabled while splitting. It is needed by:"
+                                        }
+                                        AskingKind::MakingForce => {
+                                            "Is disabled after forcing, but it is needed by:"
+                                        }
+                                    })
+                                    .color(color::SUBTEXT0)
+                                    .size(15.0),
+                                );
+                                for dependant in &ask.depended_by {
+                                    let name = self
+                                        .modpack
+                                        .plugins()
+                                        .get(dependant)
+                                        .map_or(dependant.as_str(), |v| &v.metadata.name);
+                                    ui.label(
+                                        RichText::new(format!("• {name}"))
+                                            .color(color::SUBTEXT1)
+                                            .size(15.0),
+                                    );
+                                }
+
+                                ui.add_space(6.0);
+                                let mut action = |text: &str, value: Resolution| {
+                                    let response = ui.add(
+                                        Label::new(RichText::new(text).color(color::BLUE).size(15.0))
+                                            .sense(Sense::click()),
+                                    );
+                                    if response.clicked() {
+                                        answer = Some((ask.id.clone(), value));
+                                    }
+                                };
+                                action("Enable anyway", Resolution::EnableAnyway);
+                                action(
+                                    "Keep disabled and disable the mods needing it",
+                                    Resolution::DisableDependants,
+                                );
+                                if plugin.forced_status.is_some() {
+                                    action("Remove force", Resolution::RemoveForce);
+                                }
+                            });
+                        ui.add_space(8.0);
+                    }
+                });
+            });
+
+        if let Some((id, action)) = answer {
+            self.answer(&id, action);
+        }
+    }
+
+    fn answer(&mut self, id: &str, resolution: Resolution) {
+        let Some(ask) = self.to_ask.iter().find(|ask| ask.id == id) else {
+            return;
+        };
+        let kind = ask.kind;
+
+        self.modpack.resolve(id, resolution);
+        self.update(kind);
+    }
+}
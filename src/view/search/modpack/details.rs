@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use eframe::egui::{Color32, Grid, Rect, RichText, Sense, Stroke, Ui, Vec2};
+use splinter_core::{PlatformStatus, Plugin, PluginMetadata, PluginStatus};
+
+use crate::ui::color;
+use crate::view::search::modpack::plugin::PLUGIN_HEIGHT;
+use crate::view::search::modpack::ModpackView;
+
+impl ModpackView {
+    /// Opens the details of the plugin, or closes them if they are open.
+    pub(super) fn toggle_details(&mut self, id: &str) {
+        if !self.details.remove(id) {
+            self.details.insert(id.to_string());
+        }
+    }
+
+    /// Shows everything known about the plugin, below its row.
+    pub(super) fn details_ui(&self, ui: &mut Ui, plugin: &Plugin) {
+        let metadata = &plugin.metadata;
+        let row = |ui: &mut Ui, name: &str, value: &str| {
+            ui.label(RichText::new(name).color(color::SUBTEXT0));
+            ui.label(RichText::new(value).color(color::TEXT));
+            ui.end_row();
+        };
+
+        ui.horizontal(|ui| {
+            ui.add_space(PLUGIN_HEIGHT + 12.0);
+            Grid::new(("details", &metadata.id))
+                .num_columns(2)
+                .spacing(Vec2::new(16.0, 4.0))
+                .show(ui, |ui| {
+                    row(ui, "Id", &metadata.id);
+                    row(ui, "Version", &metadata.version);
+                    if !metadata.provides.is_empty() {
+                        row(ui, "Provides", &metadata.provides.join(", "));
+                    }
+                    if !metadata.contains.is_empty() {
+                        ui.label(RichText::new("Contains").color(color::SUBTEXT0));
+                        ui.vertical(|ui| contains_ui(ui, metadata, 0));
+                        ui.end_row();
+                    }
+                    if !metadata.depends_on.is_empty() {
+                        ui.label(RichText::new("Depends on").color(color::SUBTEXT0));
+                        ui.vertical(|ui| self.dependencies_ui(ui, plugin));
+                        ui.end_row();
+                    }
+
+                    let path = plugin.path();
+                    row(
+                        ui,
+                        "Path",
+                        &path.parent().unwrap_or(path).display().to_string(),
+                    );
+                    let file_name = |path: &Path| {
+                        path.file_name()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .to_string()
+                    };
+                    row(ui, "File", &file_name(path));
+                    let original = self.modpack.journal().original(path);
+                    if original != path {
+                        row(ui, "Original file", &file_name(original));
+                    }
+                    row(ui, "Size", &format_size(plugin.size()));
+                    row(ui, "SHA-1", plugin.sha1());
+
+                    ui.label(RichText::new("History").color(color::SUBTEXT0));
+                    ui.horizontal(|ui| self.history_ui(ui, &metadata.id));
+                    ui.end_row();
+                });
+        });
+    }
+
+    /// Lists the dependencies of the plugin, and if the installed mods satisfy them.
+    fn dependencies_ui(&self, ui: &mut Ui, plugin: &Plugin) {
+        for dependency in &plugin.metadata.depends_on {
+            let required = if dependency.version.is_any() {
+                dependency.id.to_string()
+            } else {
+                format!("{} {}", dependency.id, dependency.version)
+            };
+            let provider = self.modpack.plugins().get(&dependency.id);
+            let (state, color) = match (self.modpack.plugins().satisfies(dependency), provider) {
+                (Some(true), Some(provider)) if provider.enabled() => (
+                    format!("{} is enabled", provider.metadata.name),
+                    color::GREEN,
+                ),
+                (Some(true), Some(provider)) => (
+                    format!("{} is disabled", provider.metadata.name),
+                    color::YELLOW,
+                ),
+                (Some(false), Some(provider)) => (
+                    format!(
+                        "{} {} is installed",
+                        provider.metadata.name,
+                        provider
+                            .metadata
+                            .module_version(&dependency.id)
+                            .unwrap_or(&provider.metadata.version)
+                    ),
+                    color::RED,
+                ),
+                _ => match self.modpack.platform().check(dependency) {
+                    Some(PlatformStatus::Satisfied) => ("installed".to_string(), color::GREEN),
+                    Some(PlatformStatus::Unknown) => ("not checked".to_string(), color::SUBTEXT0),
+                    Some(PlatformStatus::Mismatch(installed)) => {
+                        (format!("{installed} is installed"), color::RED)
+                    }
+                    Some(PlatformStatus::Missing) | None => {
+                        ("not installed".to_string(), color::RED)
+                    }
+                },
+            };
+
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(required).color(color::TEXT));
+                ui.label(RichText::new(state).color(color));
+            });
+        }
+    }
+
+    /// Draws the status the plugin had on every step of the search, the current step outlined.
+    fn history_ui(&self, ui: &mut Ui, id: &str) {
+        ui.spacing_mut().item_spacing.x = 2.0;
+        for (i, state) in self.modpack.states().iter().enumerate() {
+            let status = state.plugins.get(id).copied();
+            let (rect, response) = ui.allocate_exact_size(Vec2::splat(12.0), Sense::hover());
+            let fill = match status {
+                Some(PluginStatus::Enabled) => color::GREEN,
+                Some(PluginStatus::Disabled) => color::RED,
+                Some(PluginStatus::NotTheProblem) => color::SUBTEXT0,
+                None => Color32::TRANSPARENT,
+            };
+            let stroke = if i + 1 == self.modpack.step() {
+                Stroke::new(2.0, color::TEXT)
+            } else {
+                Stroke::NONE
+            };
+            ui.painter().rect(
+                Rect::from_center_size(rect.center(), rect.size() * 0.8),
+                2.0,
+                fill,
+                stroke,
+            );
+            response.on_hover_text(format!(
+                "Step {}: {}",
+                i + 1,
+                status.map_or("Unknown", |status| status.name())
+            ));
+        }
+    }
+}
+
+/// Lists the mods nested in the jar as a tree.
+pub(super) fn contains_ui(ui: &mut Ui, metadata: &PluginMetadata, depth: usize) {
+    for nested in &metadata.contains {
+        ui.label(
+            RichText::new(format!(
+                "{}└ {} {}",
+                "  ".repeat(depth),
+                nested.id,
+                nested.version
+            ))
+            .color(color::SUBTEXT0),
+        );
+        contains_ui(ui, nested, depth + 1);
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    for unit in ["B", "KB", "MB"] {
+        if size < 1024.0 {
+            return match unit {
+                "B" => format!("{bytes} B"),
+                _ => format!("{size:.1} {unit}"),
+            };
+        }
+        size /= 1024.0;
+    }
+    format!("{size:.1} GB")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::search::modpack::details::format_size;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GB");
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::{HashMap, HashSet};
+
+use eframe::egui::{
+    Align2, Color32, FontId, Id, Label, Pos2, Rect, RichText, Sense, Stroke, Ui, Vec2,
+    show_tooltip_at_pointer,
+};
+
+use eframe::epaint::CubicBezierShape;
+
+use splinter_core::{PluginList, PluginStatus};
+
+use crate::ui::color;
+use crate::view::search::modpack::details::contains_ui;
+use crate::view::search::modpack::ModpackView;
+
+const NODE_SIZE: Vec2 = Vec2::new(200.0, 26.0);
+const LAYER_SPACING: f32 = 300.0;
+const ROW_SPACING: f32 = 34.0;
+/// Below this zoom the names are not readable anymore, so they are not drawn.
+const MIN_TEXT_ZOOM: f32 = 0.4;
+
+/// The dependency graph of the modpack, with the dependants of a mod on its left and its dependencies on its right.
+pub struct GraphView {
+    focus: String,
+    zoom: f32,
+    pan: Vec2,
+    positions: HashMap<String, Pos2>,
+    edges: Vec<Edge>,
+}
+
+struct Edge {
+    dependant: String,
+    dependency: String,
+    /// If the installed version is one the dependant accepts.
+    satisfied: bool,
+}
+
+impl GraphView {
+    pub fn new(plugins: &PluginList, focus: String) -> GraphView {
+        let mut edges = vec![];
+        for plugin in plugins.iter() {
+            for dependency in &plugin.metadata.depends_on {
+                // Dependencies on a provided or nested id point to the jar which has it.
+                let Some(value) = plugins.get(&dependency.id) else {
+                    continue;
+                };
+                if value.metadata.id != plugin.metadata.id {
+                    edges.push(Edge {
+                        dependant: plugin.metadata.id.clone(),
+                        dependency: value.metadata.id.clone(),
+                        satisfied: plugins.satisfies(dependency) == Some(true),
+                    });
+                }
+            }
+        }
+
+        let ids: Vec<&str> = plugins
+            .iter()
+            .iter()
+            .map(|v| v.metadata.id.as_str())
+            .collect();
+        let layers = layers(
+            &ids,
+            &edges
+                .iter()
+                .map(|edge| (edge.dependant.as_str(), edge.dependency.as_str()))
+                .collect::<Vec<_>>(),
+        );
+
+        let mut by_layer: HashMap<usize, Vec<&str>> = HashMap::new();
+        for id in &ids {
+            by_layer.entry(layers[id]).or_default().push(id);
+        }
+        let mut positions = HashMap::new();
+        for (layer, mut ids) in by_layer {
+            ids.sort_by_key(|id| plugins.get(id).map(|v| v.metadata.name.to_lowercase()));
+            let offset = ids.len() as f32 / 2.0;
+            for (i, id) in ids.into_iter().enumerate() {
+                positions.insert(
+                    id.to_string(),
+                    Pos2::new(
+                        -(layer as f32) * LAYER_SPACING,
+                        (i as f32 - offset) * ROW_SPACING,
+                    ),
+                );
+            }
+        }
+
+        let mut view = GraphView {
+            focus,
+            zoom: 1.0,
+            pan: Vec2::ZERO,
+            positions,
+            edges,
+        };
+        view.center_on_focus();
+        view
+    }
+
+    fn center_on_focus(&mut self) {
+        if let Some(pos) = self.positions.get(&self.focus) {
+            self.pan = -pos.to_vec2() * self.zoom;
+        }
+    }
+
+    /// The mods which depend on the focused mod, and the ones it depends on.
+    fn neighbours(&self) -> HashSet<&str> {
+        let mut neighbours = HashSet::new();
+        for edge in &self.edges {
+            if edge.dependant == self.focus {
+                neighbours.insert(edge.dependency.as_str());
+            }
+            if edge.dependency == self.focus {
+                neighbours.insert(edge.dependant.as_str());
+            }
+        }
+        neighbours
+    }
+}
+
+/// Puts every mod one layer after the deepest of its dependencies, so the libraries end up in layer 0.
+/// A dependency cycle is cut at the mod where it got entered.
+fn layers<'a>(ids: &[&'a str], edges: &[(&'a str, &'a str)]) -> HashMap<&'a str, usize> {
+    let mut dependencies: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (dependant, dependency) in edges {
+        dependencies.entry(dependant).or_default().push(dependency);
+    }
+
+    fn layer<'a>(
+        id: &'a str,
+        dependencies: &HashMap<&'a str, Vec<&'a str>>,
+        layers: &mut HashMap<&'a str, usize>,
+        visiting: &mut HashSet<&'a str>,
+    ) -> usize {
+        if let Some(layer) = layers.get(id) {
+            return *layer;
+        }
+        if !visiting.insert(id) {
+            return 0;
+        }
+
+        let layer = dependencies
+            .get(id)
+            .into_iter()
+            .flatten()
+            .map(|dependency| layer(dependency, dependencies, layers, visiting) + 1)
+            .max()
+            .unwrap_or(0);
+        visiting.remove(id);
+        layers.insert(id, layer);
+        layer
+    }
+
+    let mut layers = HashMap::new();
+    for id in ids {
+        layer(id, &dependencies, &mut layers, &mut HashSet::new());
+    }
+    layers
+}
+
+impl ModpackView {
+    /// The chain of mods which got the mod enabled, starting with the mod itself.
+    fn enabled_path(&self, id: &str) -> Vec<String> {
+        let mut path = vec![id.to_string()];
+        while let Some(dependant) = self.modpack.enabled_by().get(path.last().unwrap()) {
+            if path.contains(dependant) {
+                break;
+            }
+            path.push(dependant.clone());
+        }
+        path
+    }
+
+    pub(super) fn graph_ui(&mut self, ui: &mut Ui) {
+        let Some(graph) = &self.graph else {
+            return;
+        };
+        let name = |id: &str| {
+            self.modpack
+                .plugins()
+                .get(id)
+                .map_or(id.to_string(), |v| v.metadata.name.clone())
+        };
+
+        let mut close = false;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            close = ui
+                .add(
+                    Label::new(
+                        RichText::new("Back to the mods")
+                            .color(color::BLUE)
+                            .size(16.0),
+                    )
+                    .sense(Sense::click()),
+                )
+                .clicked();
+            ui.add_space(16.0);
+            ui.label(
+                RichText::new(name(&graph.focus))
+                    .color(color::TEXT)
+                    .size(16.0),
+            );
+        });
+
+        let path = self.enabled_path(&graph.focus);
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            let text = match path.len() {
+                1 => "Scroll to zoom, drag to move, click a mod to focus it".to_string(),
+                _ => format!(
+                    "Enabled because {} needs it",
+                    path[1..]
+                        .iter()
+                        .map(|id| name(id))
+                        .collect::<Vec<_>>()
+                        .join(", which is needed by ")
+                ),
+            };
+            ui.label(RichText::new(text).color(color::SUBTEXT0).size(15.0));
+        });
+        ui.add_space(8.0);
+
+        if close {
+            self.graph = None;
+            return;
+        }
+        let focus = self.canvas_ui(ui, &path);
+        if let (Some(focus), Some(graph)) = (focus, &mut self.graph) {
+            graph.focus = focus;
+        }
+    }
+
+    /// Draws the graph, returns the mod the user clicked.
+    fn canvas_ui(&mut self, ui: &mut Ui, path: &[String]) -> Option<String> {
+        let graph = self.graph.as_mut()?;
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 8.0, color::CRUST);
+
+        graph.pan += response.drag_delta();
+        if response.hovered() {
+            let (scroll, zoom) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor = zoom * (scroll / 400.0).exp();
+            if factor != 1.0 {
+                let new_zoom = (graph.zoom * factor).clamp(0.1, 3.0);
+                // Zooms around the pointer, so the mod below it stays there.
+                if let Some(pointer) = response.hover_pos() {
+                    let anchor = pointer - rect.center() - graph.pan;
+                    graph.pan -= anchor * (new_zoom / graph.zoom - 1.0);
+                }
+                graph.zoom = new_zoom;
+            }
+        }
+
+        let graph = self.graph.as_ref()?;
+        let zoom = graph.zoom;
+        let origin = rect.center() + graph.pan;
+        let to_screen = |pos: Pos2| origin + pos.to_vec2() * zoom;
+        let node_rect = |id: &str| {
+            graph
+                .positions
+                .get(id)
+                .map(|pos| Rect::from_center_size(to_screen(*pos), NODE_SIZE * zoom))
+        };
+
+        let neighbours = graph.neighbours();
+        let on_path = |dependant: &str, dependency: &str| {
+            path.windows(2)
+                .any(|v| v[0] == dependency && v[1] == dependant)
+        };
+
+        for edge in &graph.edges {
+            let (Some(from), Some(to)) = (node_rect(&edge.dependant), node_rect(&edge.dependency))
+            else {
+                continue;
+            };
+            let (from, to) = (from.right_center(), to.left_center());
+            if !rect.intersects(Rect::from_two_pos(from, to)) {
+                continue;
+            }
+
+            let focused = edge.dependant == graph.focus || edge.dependency == graph.focus;
+            let stroke = if on_path(&edge.dependant, &edge.dependency) {
+                Stroke::new(3.0, color::YELLOW)
+            } else if !edge.satisfied {
+                Stroke::new(
+                    1.5,
+                    color::RED.gamma_multiply(if focused { 1.0 } else { 0.4 }),
+                )
+            } else if focused {
+                Stroke::new(1.5, color::SUBTEXT1)
+            } else {
+                Stroke::new(1.0, color::SUBTEXT0.gamma_multiply(0.25))
+            };
+            let bend = Vec2::new((to.x - from.x).abs() / 2.0, 0.0);
+            painter.add(CubicBezierShape::from_points_stroke(
+                [from, from + bend, to - bend, to],
+                false,
+                Color32::TRANSPARENT,
+                stroke,
+            ));
+        }
+
+        let mut clicked = None;
+        let pointer = response.hover_pos();
+        for plugin in self.modpack.plugins().iter() {
+            let id = plugin.metadata.id.as_str();
+            let Some(node) = node_rect(id) else {
+                continue;
+            };
+            if !rect.intersects(node) {
+                continue;
+            }
+
+            let status = match plugin.status {
+                PluginStatus::Enabled => color::GREEN,
+                PluginStatus::Disabled => color::RED,
+                PluginStatus::NotTheProblem => color::SUBTEXT0,
+            };
+            let focused = id == graph.focus;
+            let faded = !focused && !neighbours.contains(id) && !path.iter().any(|v| v == id);
+            let opacity = if faded { 0.35 } else { 1.0 };
+            let stroke = match plugin.forced_status {
+                Some(_) => Stroke::new(2.0, color::BLUE.gamma_multiply(opacity)),
+                None => Stroke::new(
+                    if focused { 2.0 } else { 1.0 },
+                    status.gamma_multiply(opacity),
+                ),
+            };
+            painter.rect(
+                node,
+                6.0 * zoom,
+                if focused { color::PANEL } else { color::MANTLE },
+                stroke,
+            );
+            painter.circle_filled(
+                node.left_center() + Vec2::new(10.0 * zoom, 0.0),
+                4.0 * zoom,
+                status.gamma_multiply(opacity),
+            );
+            if zoom >= MIN_TEXT_ZOOM {
+                painter.with_clip_rect(node.shrink(4.0 * zoom)).text(
+                    node.left_center() + Vec2::new(20.0 * zoom, 0.0),
+                    Align2::LEFT_CENTER,
+                    &plugin.metadata.name,
+                    FontId::proportional(14.0 * zoom),
+                    color::TEXT.gamma_multiply(opacity),
+                );
+            }
+
+            if pointer.is_some_and(|pos| node.contains(pos)) {
+                show_tooltip_at_pointer(ui.ctx(), Id::new("graph-node").with(id), |ui| {
+                    ui.label(
+                        RichText::new(&plugin.metadata.name)
+                            .color(color::TEXT)
+                            .strong(),
+                    );
+                    ui.label(format!(
+                        "{} {}",
+                        plugin.metadata.id, plugin.metadata.version
+                    ));
+                    ui.label(plugin.status.name());
+                    if !plugin.metadata.provides.is_empty() {
+                        ui.label(format!("Provides {}", plugin.metadata.provides.join(", ")));
+                    }
+                    contains_ui(ui, &plugin.metadata, 0);
+                });
+                if response.clicked() {
+                    clicked = Some(id.to_string());
+                }
+            }
+        }
+
+        clicked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::view::search::modpack::graph::layers;
+
+    #[test]
+    fn layered() {
+        let layers = layers(
+            &["lib", "api", "mod", "addon", "a", "b"],
+            &[
+                ("api", "lib"),
+                ("mod", "api"),
+                ("mod", "lib"),
+                ("addon", "mod"),
+                ("a", "b"),
+                ("b", "a"),
+            ],
+        );
+        assert_eq!(layers["lib"], 0);
+        assert_eq!(layers["api"], 1);
+        assert_eq!(layers["mod"], 2);
+        assert_eq!(layers["addon"], 3);
+        // Cycles do not loop forever.
+        assert_eq!(layers["a"].abs_diff(layers["b"]), 1);
+    }
+}
@@ -0,0 +1,147 @@
+use eframe::egui::{Label, RichText, Sense, Ui};
+use splinter_core::ForceImpact;
+
+use crate::ui::color;
+use crate::view::search::modpack::plugin::{next_forced, PLUGIN_HEIGHT};
+use crate::view::search::modpack::ModpackView;
+
+/// A change to the forced status of a plugin which would break other mods, it waits for the user to confirm it.
+pub struct PendingForce {
+    pub id: String,
+    pub forced: Option<bool>,
+    impact: ForceImpact,
+}
+
+enum ImpactChoice {
+    Apply,
+    /// Applies the change, and forces off the mods it would break.
+    Cascade,
+    Cancel,
+}
+
+impl ModpackView {
+    /// Moves the plugin to the next forced status, clicking again while the change is pending skips to the one after.
+    /// Changes which break other mods are shown first, the others get collected in `forced` right away.
+    pub(super) fn cycle_forced(&mut self, id: &str, forced: &mut Vec<String>) {
+        let Some(plugin) = self.modpack.plugins().get(id) else {
+            return;
+        };
+        let current = match self.pending_force.take() {
+            Some(pending) if pending.id == id => pending.forced,
+            _ => plugin.forced_status,
+        };
+        let next = next_forced(current);
+        if next == plugin.forced_status {
+            // Went around the cycle, so there is nothing to change.
+            return;
+        }
+
+        let impact = self.modpack.plugins().force_impact(id, next);
+        if impact.is_empty() {
+            self.force(id, next, forced);
+        } else {
+            self.pending_force = Some(PendingForce {
+                id: id.to_string(),
+                forced: next,
+                impact,
+            });
+        }
+    }
+
+    fn force(&mut self, id: &str, status: Option<bool>, forced: &mut Vec<String>) {
+        if let Some(plugin) = self.modpack.plugins_mut().get_mut(id) {
+            plugin.forced_status = status;
+            forced.push(id.to_string());
+        }
+    }
+
+    /// Lists what the pending change of the plugin would break below its row, with the choice to go through with it.
+    pub(super) fn force_impact_ui(&mut self, ui: &mut Ui, id: &str, forced: &mut Vec<String>) {
+        let Some(pending) = self.pending_force.as_ref().filter(|pending| pending.id == id) else {
+            return;
+        };
+
+        let plugins = self.modpack.plugins();
+        let name = |id: &str| plugins.get(id).map_or(id.to_string(), |v| v.metadata.name.clone());
+        let impact = &pending.impact;
+        let cascade = impact.cascade(id);
+        let mut choice = None;
+        ui.horizontal(|ui| {
+            ui.add_space(PLUGIN_HEIGHT + 12.0);
+            ui.vertical(|ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "{} would break other mods:",
+                        match pending.forced {
+                            Some(false) => "Forcing it off",
+                            Some(true) => "Forcing it on",
+                            None => "Removing the force",
+                        }
+                    ))
+                    .color(color::YELLOW)
+                    .size(16.0),
+                );
+                let line = |ui: &mut Ui, text: String| {
+                    ui.label(RichText::new(format!("• {text}")).color(color::SUBTEXT1).size(15.0));
+                };
+                for (dependant, dependency) in &impact.missing_dependencies {
+                    line(ui, format!("{} no longer finds {dependency}", name(dependant)));
+                }
+                if !impact.removed_modules.is_empty() {
+                    line(
+                        ui,
+                        format!("The nested {} no longer load", impact.removed_modules.join(", ")),
+                    );
+                }
+                for (v0, v1) in &impact.breaks {
+                    line(ui, format!("{} is incompatible with {}", name(v0), name(v1)));
+                }
+
+                ui.add_space(4.0);
+                ui.horizontal(|ui| {
+                    let mut action = |text: String, value: ImpactChoice| {
+                        let response = ui.add(
+                            Label::new(RichText::new(text).color(color::BLUE).size(15.0))
+                                .sense(Sense::click()),
+                        );
+                        if response.clicked() {
+                            choice = Some(value);
+                        }
+                    };
+                    action("Apply anyway".to_string(), ImpactChoice::Apply);
+                    if !cascade.is_empty() {
+                        action(
+                            format!(
+                                "Apply and force off {}",
+                                match cascade.as_slice() {
+                                    [other] => name(other),
+                                    _ => format!("{} mods", cascade.len()),
+                                }
+                            ),
+                            ImpactChoice::Cascade,
+                        );
+                    }
+                    action("Cancel".to_string(), ImpactChoice::Cancel);
+                });
+            });
+        });
+        ui.add_space(8.0);
+
+        let Some(choice) = choice else {
+            return;
+        };
+        let Some(pending) = self.pending_force.take() else {
+            return;
+        };
+        match choice {
+            ImpactChoice::Apply => self.force(id, pending.forced, forced),
+            ImpactChoice::Cascade => {
+                self.force(id, pending.forced, forced);
+                for other in cascade {
+                    self.force(&other, Some(false), forced);
+                }
+            }
+            ImpactChoice::Cancel => {}
+        }
+    }
+}
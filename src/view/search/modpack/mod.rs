@@ -0,0 +1,286 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use eframe::egui::{Context, FontFamily, FontId, RichText, ScrollArea, TextureHandle, TextureOptions, Ui};
+use splinter_core::{describe_unsatisfied, Modpack, PluginStatus, SearchConfig};
+use splinter_event::EventTracker;
+
+use ask::{AskingEnable, AskingKind};
+use graph::GraphView;
+use impact::PendingForce;
+use plugin::{plugin_ui, PluginChange};
+
+use crate::{ApplicationState, ModpackStatus};
+use crate::ui::{color, instance_icon, load_texture, ProgressStatus};
+use crate::view::ProgressEvent;
+
+mod ask;
+mod details;
+mod graph;
+mod impact;
+mod plugin;
+mod search;
+
+#[derive(Debug)]
+pub enum ModpackOperationEvent {
+    Undo,
+    Redo,
+    Split,
+    Invert,
+}
+
+/// Shows the modpack being searched, and lets the user answer the tests.
+pub struct ModpackView {
+    modpack: Modpack,
+    // The icon of the instance, and the icons of the plugins by id.
+    icon: Option<TextureHandle>,
+    icons: HashMap<String, TextureHandle>,
+
+    display_order: Vec<Vec<String>>,
+    // This contains the list of mod-ids which splinter is going to ask the user to enable.
+    to_ask: Vec<AskingEnable>,
+    // Shown instead of the list of mods while set.
+    graph: Option<GraphView>,
+    // The plugins which have their details open.
+    details: HashSet<String>,
+    // The change to a forced status which would break other mods, until the user decides on it.
+    pending_force: Option<PendingForce>,
+    // The search config while its sliders are dragged, it only gets saved once they are released.
+    dragged_config: Option<SearchConfig>,
+
+    tracker: EventTracker,
+}
+
+impl ModpackView {
+    pub fn new(path: PathBuf, ctx: &Context) -> Option<ModpackView> {
+        let modpack = Modpack::new(path)?;
+        Some(ModpackView {
+            icon: instance_icon(modpack.metadata(), ctx),
+            modpack,
+            icons: HashMap::new(),
+            display_order: vec![],
+            to_ask: vec![],
+            graph: None,
+            details: HashSet::new(),
+            pending_force: None,
+            dragged_config: None,
+            tracker: EventTracker::new(),
+        })
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, state: &mut ApplicationState) {
+        let mut commander = self.tracker.tick(&mut state.events);
+        if self.modpack.is_loading() {
+            ui.ctx().request_repaint();
+            let progress = self.tick_loader(ui.ctx());
+            commander.dispatch(ProgressEvent(progress.map(ProgressStatus::Determinate)));
+        }
+
+        for event in commander.consume::<ModpackOperationEvent>() {
+            match event {
+                ModpackOperationEvent::Undo => self.modpack.undo(),
+                ModpackOperationEvent::Redo => self.modpack.redo(),
+                ModpackOperationEvent::Split => self.modpack.split(),
+                ModpackOperationEvent::Invert => self.modpack.invert(),
+            }
+            self.update(AskingKind::SplitDependency);
+        }
+        self.platform_ui(ui);
+        self.search_ui(ui);
+        self.ask_ui(ui);
+
+        let mut forced = Vec::new();
+        let mut stability_changed = false;
+        if self.graph.is_some() {
+            self.graph_ui(ui);
+        } else {
+            self.plugins_ui(ui, &mut forced, &mut stability_changed);
+        }
+        if !forced.is_empty() {
+            self.modpack.apply_forced(&forced);
+            self.update(AskingKind::MakingForce);
+        }
+        if stability_changed {
+            self.modpack.save_session();
+        }
+        for notification in self.modpack.take_notifications() {
+            commander.dispatch(notification);
+        }
+
+        state.modpack_status = ModpackStatus::Active {
+            path: self.modpack.path().clone(),
+            title: self.modpack.title(),
+            icon: self.icon.clone(),
+            is_loaded: !self.modpack.is_loading(),
+            can_undo: self.modpack.can_undo(),
+            can_redo: self.modpack.can_redo(),
+            step: self.modpack.step(),
+            steps: self.modpack.steps(),
+            estimate: self.modpack.estimate(),
+        };
+    }
+
+    /// Adds the plugins which finished loading along with their icons, returns how far the loading is.
+    fn tick_loader(&mut self, ctx: &Context) -> Option<f32> {
+        let i = self.modpack.plugins().iter().len();
+        let progress = self.modpack.tick_loader();
+
+        let plugins = self.modpack.plugins().iter();
+        for plugin in &plugins[i..] {
+            if let Some(icon) = plugin.icon() {
+                let texture = load_texture(ctx, &plugin.metadata.id, icon, TextureOptions::NEAREST);
+                self.icons.insert(plugin.metadata.id.clone(), texture);
+            }
+        }
+        if progress.is_none() {
+            // A recovered session got applied, which may have left dependencies disabled.
+            self.update(AskingKind::SplitDependency);
+        } else if plugins.len() != i {
+            self.update_display_order();
+        }
+        progress
+    }
+
+    /// Ends the search, every mod which is not forced gets enabled again.
+    pub fn end_session(&mut self) {
+        self.modpack.end_session();
+    }
+
+    /// Ends the search, and gives every jar back the file name it had before the session started.
+    pub fn restore(&mut self) {
+        self.modpack.restore();
+    }
+
+    /// Lists the mods by status, collecting the ones which got forced and if a stability changed.
+    fn plugins_ui(&mut self, ui: &mut Ui, forced: &mut Vec<String>, stability_changed: &mut bool) {
+        let mut show_graph = None;
+        let display_order = self.display_order.clone();
+        ScrollArea::vertical().show(ui, |ui| {
+            for (i, plugins) in display_order.iter().enumerate() {
+                let status = PluginStatus::iter()[i];
+                ui.horizontal(|ui| {
+                    ui.add_space(8.0);
+                    ui.label(
+                        RichText::new(match status {
+                            PluginStatus::Disabled => "Disabled Mods",
+                            PluginStatus::Enabled => "Enabled Mods",
+                            PluginStatus::NotTheProblem => "Not faulty",
+                        })
+                        .color(color::TEXT)
+                        .font(FontId::new(
+                            18.0,
+                            FontFamily::Name(Arc::from("Roboto-Bold")),
+                        )),
+                    );
+                });
+                ui.add_space(4.0);
+                for id in plugins {
+                    if let Some(plugin) = self.modpack.plugins_mut().get_mut(id) {
+                        match plugin_ui(ui, plugin, self.icons.get(id)) {
+                            Some(PluginChange::Forced) => self.cycle_forced(id, forced),
+                            Some(PluginChange::Stability) => *stability_changed = true,
+                            Some(PluginChange::ShowGraph) => show_graph = Some(id.clone()),
+                            Some(PluginChange::ToggleDetails) => self.toggle_details(id),
+                            None => {}
+                        }
+                        ui.add_space(8.0);
+                        self.force_impact_ui(ui, id, forced);
+                        if let Some(plugin) = self.modpack.plugins().get(id) {
+                            if self.details.contains(id) {
+                                self.details_ui(ui, plugin);
+                                ui.add_space(8.0);
+                            }
+                        }
+                    }
+                }
+                ui.add_space(8.0);
+            }
+        });
+        if let Some(id) = show_graph {
+            self.graph = Some(GraphView::new(self.modpack.plugins(), id));
+        }
+    }
+
+    /// Shows what the instance runs on, and the dependencies on it which are not going to load.
+    fn platform_ui(&self, ui: &mut Ui) {
+        let platform = self.modpack.platform();
+        let components: Vec<String> = platform
+            .components
+            .iter()
+            .filter_map(|component| Some(format!("{} {}", component.name, component.version.as_ref()?)))
+            .collect();
+
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(
+                RichText::new(if platform.detected {
+                    components.join(" · ")
+                } else {
+                    "Unknown platform, dependencies on the game and loader are not checked".to_string()
+                })
+                .color(color::SUBTEXT0)
+                .size(16.0),
+            );
+        });
+
+        if !self.modpack.is_loading() {
+            for (plugin, dependency, installed) in self.modpack.plugins().unsatisfied_dependencies(platform) {
+                ui.horizontal(|ui| {
+                    ui.add_space(8.0);
+                    ui.label(
+                        RichText::new(describe_unsatisfied(plugin, dependency, installed.as_deref()))
+                            .color(color::RED)
+                            .size(16.0),
+                    );
+                });
+            }
+        }
+        ui.add_space(8.0);
+    }
+
+    /// Catches up with the statuses after the search changed them.
+    fn update(&mut self, kind: AskingKind) {
+        self.update_ask(kind);
+        self.update_display_order();
+    }
+
+    /// Updates the list of mods which we advise the user to enable
+    fn update_ask(&mut self, kind: AskingKind) {
+        self.to_ask.clear();
+        for id in self.modpack.get_dependant_disabled_mods().into_keys() {
+            self.to_ask.push(AskingEnable {
+                depended_by: self.modpack.find_dependants(&id),
+                id,
+                kind,
+            });
+        }
+    }
+
+    fn update_display_order(&mut self) {
+        // What a pending change breaks depends on the statuses, so it has to be asked for again.
+        self.pending_force = None;
+        let plugins = self.modpack.plugins();
+        self.display_order.clear();
+        for status in PluginStatus::iter() {
+            let mut ids = Vec::new();
+            for plugin in plugins.iter() {
+                if plugin.status == status {
+                    ids.push(plugin.metadata.id.clone());
+                }
+            }
+
+            if !ids.is_empty() {
+                ids.sort_by(|v0, v1| {
+                    plugins
+                        .get(v0)
+                        .unwrap()
+                        .metadata
+                        .id
+                        .cmp(&plugins.get(v1).unwrap().metadata.id)
+                });
+                self.display_order.push(ids);
+            }
+        }
+    }
+}
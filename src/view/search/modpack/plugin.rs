@@ -0,0 +1,181 @@
+use eframe::egui::load::SizedTexture;
+use eframe::egui::{show_tooltip_text, Align, Color32, Id, LayerId, Layout, Order, RichText, Sense, Slider, TextureHandle, Ui, Vec2};
+
+use splinter_animation::Lerp;
+use splinter_core::{Plugin, MAX_STABILITY};
+use splinter_icon::icon;
+
+use crate::ui::icon::Icon;
+use crate::ui::{animation, color};
+
+pub const PLUGIN_HEIGHT: f32 = 32.0;
+
+/// What the user did in the row of a plugin.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum PluginChange {
+    /// Asked for the next forced status, see [`next_forced`].
+    Forced,
+    Stability,
+    /// Asked for the dependency graph around the plugin.
+    ShowGraph,
+    /// Opened or closed the details of the plugin.
+    ToggleDetails,
+}
+
+/// Draws the plugin, returns what the user changed.
+/// Clicking asks to cycle the forced status, and the stability can be changed with a right click.
+pub fn plugin_ui(ui: &mut Ui, plugin: &mut Plugin, icon: Option<&TextureHandle>) -> Option<PluginChange> {
+    let mut vec2 = ui.available_size_before_wrap();
+    vec2.y = PLUGIN_HEIGHT;
+    let enabled = plugin.enabled();
+    let mut change = None;
+    ui.allocate_ui_with_layout(vec2, Layout::left_to_right(Align::Center), |ui| {
+        ui.add_space(4.0);
+        let rect = ui.available_rect_before_wrap();
+
+        let response = ui.with_layer_id(LayerId::new(Order::Foreground, Id::new("above")), |ui| {
+            ui.interact(
+                rect,
+                ui.next_auto_id(),
+                Sense::click_and_drag(),
+            )
+        }).inner;
+        if response.clicked() {
+            change = Some(PluginChange::Forced);
+        }
+        response.context_menu(|ui| {
+            ui.label(RichText::new("Stability").color(color::TEXT).size(16.0));
+            let mut stability = plugin.stability();
+            let slider = ui.add(Slider::new(&mut stability, 0..=MAX_STABILITY));
+            if slider.changed() {
+                plugin.stability_override = Some(stability);
+            }
+            // The session only gets saved once the user let go of the slider, not on every frame of the drag.
+            if slider.drag_stopped() || (slider.changed() && !slider.dragged()) {
+                change = Some(PluginChange::Stability);
+            }
+            if plugin.stability_override.is_some()
+                && ui
+                    .button(format!("Use automatic ({})", plugin.auto_stability))
+                    .clicked()
+            {
+                plugin.stability_override = None;
+                change = Some(PluginChange::Stability);
+                ui.close_menu();
+            }
+            ui.separator();
+            if ui.button("Details").clicked() {
+                change = Some(PluginChange::ToggleDetails);
+                ui.close_menu();
+            }
+            if ui.button("Show dependency graph").clicked() {
+                change = Some(PluginChange::ShowGraph);
+                ui.close_menu();
+            }
+        });
+
+        let animation = animation(ui);
+        let enabled = animation
+            .get(response.id)
+            .redirect(enabled as u8 as f32)
+            .get();
+
+        let mut stroke = (0.0, Color32::TRANSPARENT);
+        if response.hovered() {
+            stroke = (2.0, color::PANEL);
+        }
+
+        let bg = color::CRUST.lerp(&color::MANTLE, enabled * 0.8 + 0.2);
+        let fg = color::SUBTEXT0.lerp(&color::TEXT, enabled);
+        ui.set_min_size(rect.size());
+        ui.painter().rect(rect, 8.0, bg, stroke);
+
+        if let Some(icon) = icon {
+            let response = ui.image(SizedTexture {
+                id: icon.id(),
+                size: Vec2::new(PLUGIN_HEIGHT, PLUGIN_HEIGHT),
+            });
+            ui.painter().rect_filled(
+                response.rect,
+                0.0,
+                Color32::from_rgba_premultiplied(0, 0, 0, ((1.0 - enabled) * 128.0) as u8),
+            );
+        } else {
+            ui.add_space(PLUGIN_HEIGHT);
+        }
+
+        ui.add_space(8.0);
+        ui.label(RichText::new(&plugin.metadata.name).color(fg).size(18.0));
+
+        ui.allocate_ui_with_layout(
+            ui.available_size_before_wrap(),
+            Layout::right_to_left(Align::Center),
+            |ui| {
+                ui.add_space(8.0);
+
+                {
+                    let force_transition = animation
+                        .get(response.id.with("lock"))
+                        .redirect(plugin.forced_status.is_some() as u8 as f32)
+                        .get();
+
+                    let lock_enabled = plugin.forced_status.unwrap_or(true);
+                    ui.add_space(6.0 * force_transition);
+                    let color = fg.gamma_multiply(force_transition);
+
+                    ui.add(Icon::new(
+                        if force_transition != 1.0 {
+                            icon!("lock_open")
+                        } else {
+                            icon!("lock")
+                        },
+                        16.0,
+                        color,
+                    ));
+                    ui.add_space(6.0 * force_transition);
+                    ui.label(
+                        RichText::new(format!(
+                            "Force-{}",
+                            if lock_enabled { "enabled" } else { "disabled" }
+                        ))
+                        .color(color)
+                        .size(18.0),
+                    );
+                }
+
+                ui.add_space(12.0);
+                let color = match plugin.stability_override {
+                    Some(_) => color::BLUE,
+                    None => fg.gamma_multiply(0.6),
+                };
+                let stability = ui
+                    .horizontal(|ui| {
+                        ui.label(RichText::new(plugin.stability().to_string()).color(color).size(16.0));
+                        ui.add(Icon::new(icon!("anchor"), 16.0, color));
+                    })
+                    .response;
+                if response.hover_pos().is_some_and(|pos| stability.rect.contains(pos)) {
+                    show_tooltip_text(
+                        ui.ctx(),
+                        response.id.with("stability"),
+                        format!(
+                            "Stability {}, {}.\nStable mods get split less often, right click to change it.",
+                            plugin.stability(),
+                            if plugin.stability_override.is_some() { "set by you" } else { "automatic" }
+                        ),
+                    );
+                }
+            },
+        );
+    });
+    change
+}
+
+/// The forced status which comes after this one when clicking a plugin.
+pub fn next_forced(forced: Option<bool>) -> Option<bool> {
+    match forced {
+        None => Some(false),
+        Some(false) => Some(true),
+        Some(true) => None,
+    }
+}
@@ -0,0 +1,98 @@
+use eframe::egui::{Label, RichText, Sense, Slider, Ui};
+use splinter_core::SearchMode;
+
+use crate::ui::color;
+use crate::view::search::modpack::ModpackView;
+
+impl ModpackView {
+    /// Lets the user pick the search mode, and shows how far the search is.
+    pub(super) fn search_ui(&mut self, ui: &mut Ui) {
+        let mut selected = None;
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            ui.label(RichText::new("Search for").color(color::SUBTEXT0).size(16.0));
+            for (mode, text, tooltip) in [
+                (
+                    SearchMode::Bisect,
+                    "a single mod",
+                    "Halves the mods on every test, for issues caused by one mod.",
+                ),
+                (
+                    SearchMode::Ddmin,
+                    "a set of mods",
+                    "Finds the smallest set of mods which has the issue,\nfor issues which only happen when some mods are together.",
+                ),
+                (
+                    SearchMode::Bayes,
+                    "a flaky mod",
+                    "Keeps track of how likely every mod is the faulty one,\nfor issues which do not show up every time.\nA wrong answer gets corrected by the tests after it.",
+                ),
+            ] {
+                ui.add_space(8.0);
+                let response = ui
+                    .add(
+                        Label::new(RichText::new(text).size(16.0).color(if self.modpack.mode() == mode {
+                            color::TEXT
+                        } else {
+                            color::BLUE
+                        }))
+                        .sense(Sense::click()),
+                    )
+                    .on_hover_text(tooltip);
+                if response.clicked() && self.modpack.mode() != mode {
+                    selected = Some(mode);
+                }
+            }
+
+            if let Some(progress) = self.modpack.progress() {
+                ui.add_space(16.0);
+                ui.label(RichText::new(progress).color(color::SUBTEXT0).size(16.0));
+            }
+        });
+        ui.add_space(8.0);
+
+        if self.modpack.mode() == SearchMode::Bayes {
+            self.config_ui(ui);
+        }
+
+        if let Some(mode) = selected {
+            self.modpack.set_mode(mode);
+        }
+    }
+
+    /// Lets the user tell how flaky the issue is.
+    fn config_ui(&mut self, ui: &mut Ui) {
+        let mut config = self.dragged_config.unwrap_or_else(|| self.modpack.search_config());
+        let mut sliders = Vec::new();
+        ui.horizontal(|ui| {
+            ui.add_space(8.0);
+            sliders.push(
+                ui.add(
+                    Slider::new(&mut config.false_negative, 0.0..=0.9)
+                        .text("chance the issue does not show up")
+                        .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+                ),
+            );
+            ui.add_space(16.0);
+            sliders.push(
+                ui.add(
+                    Slider::new(&mut config.confidence, 0.5..=0.999)
+                        .text("certainty to stop at")
+                        .custom_formatter(|v, _| format!("{:.1}%", v * 100.0)),
+                ),
+            );
+        });
+        ui.add_space(8.0);
+
+        // Saving the session and estimating again on every frame of a drag would stall the window.
+        let released = sliders
+            .iter()
+            .any(|slider| slider.drag_stopped() || (slider.changed() && !slider.dragged()));
+        if released {
+            self.dragged_config = None;
+            self.modpack.set_search_config(config);
+        } else if sliders.iter().any(|slider| slider.dragged()) {
+            self.dragged_config = Some(config);
+        }
+    }
+}